@@ -12,7 +12,7 @@ pub struct Prop<'a> {
 	pub ident: &'a Option<Ident>,
 }
 
-pub fn get_props(fields: &FieldsNamed) -> Vec<Prop> {
+pub fn get_props(fields: &FieldsNamed) -> Vec<Prop<'_>> {
 	let mut props = Vec::new();
 
 	for field in fields.named.iter() {
@@ -0,0 +1,108 @@
+use crate::utils::{check_ident, x_column_name};
+use crate::{Dialect, Result};
+
+/// Aggregate column expression for the `SelectSqlBuilder` (e.g., `count(*) AS "count"`).
+///
+/// e.g., `sqlb::select().table("todo").columns(&["project_id"]).agg(Agg::count_all().alias("count")).group_by(&["project_id"])`
+#[derive(Clone)]
+pub struct Agg {
+	func: AggFunc,
+	/// None for the `*` of `count(*)`
+	column: Option<String>,
+	distinct: bool,
+	alias: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+enum AggFunc {
+	Count,
+	Sum,
+	Avg,
+	Min,
+	Max,
+}
+
+impl AggFunc {
+	fn sql(&self) -> &'static str {
+		match self {
+			AggFunc::Count => "count",
+			AggFunc::Sum => "sum",
+			AggFunc::Avg => "avg",
+			AggFunc::Min => "min",
+			AggFunc::Max => "max",
+		}
+	}
+}
+
+// region:    --- Constructors
+impl Agg {
+	fn new(func: AggFunc, column: Option<&str>) -> Self {
+		Agg {
+			func,
+			column: column.map(|c| c.to_string()),
+			distinct: false,
+			alias: None,
+		}
+	}
+
+	/// `count(*)`
+	pub fn count_all() -> Self {
+		Self::new(AggFunc::Count, None)
+	}
+
+	/// `count("column")` (does not count the NULLs)
+	pub fn count(column: &str) -> Self {
+		Self::new(AggFunc::Count, Some(column))
+	}
+
+	pub fn sum(column: &str) -> Self {
+		Self::new(AggFunc::Sum, Some(column))
+	}
+
+	pub fn avg(column: &str) -> Self {
+		Self::new(AggFunc::Avg, Some(column))
+	}
+
+	pub fn min(column: &str) -> Self {
+		Self::new(AggFunc::Min, Some(column))
+	}
+
+	pub fn max(column: &str) -> Self {
+		Self::new(AggFunc::Max, Some(column))
+	}
+}
+// endregion: --- Constructors
+
+impl Agg {
+	/// Aggregate only the distinct values (e.g., `count(DISTINCT "project_id")`).
+	/// Note: Ignored for `count_all()`.
+	pub fn distinct(mut self) -> Self {
+		self.distinct = true;
+		self
+	}
+
+	/// Column alias (e.g., `sum("amount") AS "total"`).
+	pub fn alias(mut self, alias: &str) -> Self {
+		self.alias = Some(alias.to_string());
+		self
+	}
+
+	// SQL: count(DISTINCT "project_id") AS "project_count"
+	pub(crate) fn sql(&self, dialect: Dialect) -> Result<String> {
+		let mut sql = match &self.column {
+			None => format!("{}(*)", self.func.sql()),
+			Some(column) => {
+				check_ident(column)?;
+				let distinct = if self.distinct { "DISTINCT " } else { "" };
+				format!("{}({}{})", self.func.sql(), distinct, x_column_name(dialect, column))
+			}
+		};
+
+		if let Some(alias) = &self.alias {
+			check_ident(alias)?;
+			sql.push_str(&format!(" AS {}", x_column_name(dialect, alias)));
+		}
+
+		Ok(sql)
+	}
+}
@@ -0,0 +1,335 @@
+//! `Cond` is a composable boolean condition tree for the where clauses (and/or/not with nested groups).
+//!
+//! e.g., `WHERE "a" = $1 AND ("b" = $2 OR "c" = $3)`
+//! ```
+//! use sqlb::{Cond, SqlBuilder};
+//! let sb = sqlb::select()
+//!     .table("todo")
+//!     .and_where_eq("a", 1)
+//!     .and_where_cond(Cond::eq("b", 2).or(Cond::eq("c", 3)));
+//! assert_eq!(sb.sql(), r#"SELECT * FROM "todo" WHERE "a" = $1 AND ("b" = $2 OR "c" = $3) "#);
+//! ```
+
+use crate::core::{sql_value, WhereItem};
+use crate::utils::{check_ident, x_column_name};
+use crate::val::BoxedVal;
+use crate::{Dialect, Error, Result, SelectSqlBuilder, SqlxBindable};
+
+pub struct Cond<'a> {
+	node: CondNode<'a>,
+}
+
+enum CondNode<'a> {
+	/// `"name" op $n` (or the sql of a `Raw` or `Expr` value, see `sql_value`)
+	Item(WhereItem<'a>),
+	/// `"name" = ANY($n)` or `"name" <> ALL($n)` (op being `= ANY` or `<> ALL`)
+	In(WhereItem<'a>),
+	/// `"name" IS NULL` or `"name" IS NOT NULL` (nothing bound)
+	Null {
+		name: String,
+		not: bool,
+	},
+	/// `"name" = $n`, or `"name" IS NULL` when the value is null (nothing bound then)
+	EqNullable(WhereItem<'a>),
+	/// `"left" op "right"` (column to column, nothing bound)
+	Cols {
+		left: String,
+		op: &'static str,
+		right: String,
+	},
+	/// `"name" op (SELECT ...)`
+	Select {
+		name: String,
+		op: &'static str,
+		select: Box<SelectSqlBuilder<'a>>,
+	},
+	/// `EXISTS (SELECT ...)` or `NOT EXISTS (SELECT ...)`
+	Exists {
+		select: Box<SelectSqlBuilder<'a>>,
+		not: bool,
+	},
+	And(Vec<Cond<'a>>),
+	Or(Vec<Cond<'a>>),
+	Not(Box<Cond<'a>>),
+}
+
+// region:    --- Constructors
+impl<'a> Cond<'a> {
+	/// Condition `name op $n` (e.g., `Cond::op("id", ">", 123)`).
+	pub fn op<T: 'a + SqlxBindable + Send + Sync>(name: &str, op: &'static str, val: T) -> Self {
+		WhereItem::from((name, op, val)).into()
+	}
+
+	/// Condition `name = $n`
+	pub fn eq<T: 'a + SqlxBindable + Send + Sync>(name: &str, val: T) -> Self {
+		Self::op(name, "=", val)
+	}
+
+	/// Condition `name = ANY($n)` with the list bound as one array value (e.g., a `Vec<i64>`, or an array `Expr`).
+	pub fn is_in<T: 'a + SqlxBindable + Send + Sync>(name: &str, vals: T) -> Self {
+		Cond {
+			node: CondNode::In((name, "= ANY", vals).into()),
+		}
+	}
+
+	/// Condition `name <> ALL($n)` (i.e., `NOT IN`) with the list bound as one array value.
+	pub fn not_in<T: 'a + SqlxBindable + Send + Sync>(name: &str, vals: T) -> Self {
+		Cond {
+			node: CondNode::In((name, "<> ALL", vals).into()),
+		}
+	}
+
+	/// Condition `name IS NULL`
+	pub fn is_null(name: &str) -> Self {
+		Cond {
+			node: CondNode::Null {
+				name: name.to_string(),
+				not: false,
+			},
+		}
+	}
+
+	/// Condition `name IS NOT NULL`
+	pub fn is_not_null(name: &str) -> Self {
+		Cond {
+			node: CondNode::Null {
+				name: name.to_string(),
+				not: true,
+			},
+		}
+	}
+
+	/// Condition `name = $n`, or `name IS NULL` if the value is null (e.g., `None::<String>`).
+	pub fn eq_nullable<T: 'a + SqlxBindable + Send + Sync>(name: &str, val: T) -> Self {
+		Cond {
+			node: CondNode::EqNullable((name, "=", val).into()),
+		}
+	}
+
+	/// Condition `name IS DISTINCT FROM $n` (null-safe `<>`).
+	pub fn distinct_from<T: 'a + SqlxBindable + Send + Sync>(name: &str, val: T) -> Self {
+		Self::op(name, "IS DISTINCT FROM", val)
+	}
+
+	/// Condition `name IS NOT DISTINCT FROM $n` (null-safe `=`).
+	pub fn not_distinct_from<T: 'a + SqlxBindable + Send + Sync>(name: &str, val: T) -> Self {
+		Self::op(name, "IS NOT DISTINCT FROM", val)
+	}
+
+	/// Condition `left op right` between two columns, nothing bound (e.g., `Cond::cols("t.ctime", "<", "s.ctime")`).
+	pub fn cols(left: &str, op: &'static str, right: &str) -> Self {
+		Cond {
+			node: CondNode::Cols {
+				left: left.to_string(),
+				op,
+				right: right.to_string(),
+			},
+		}
+	}
+
+	/// Condition `left = right` between two columns (e.g., `Cond::cols_eq("todo.id", "s.todo_id")`).
+	pub fn cols_eq(left: &str, right: &str) -> Self {
+		Self::cols(left, "=", right)
+	}
+
+	/// Condition `name op (SELECT ...)` (e.g., `Cond::op_select("project_id", "IN", sub_select)`).
+	/// Note: The subquery values are bound in place, with the placeholders numbered with the outer query.
+	pub fn op_select(name: &str, op: &'static str, select: SelectSqlBuilder<'a>) -> Self {
+		Cond {
+			node: CondNode::Select {
+				name: name.to_string(),
+				op,
+				select: Box::new(select),
+			},
+		}
+	}
+
+	/// Condition `EXISTS (SELECT ...)`
+	pub fn exists(select: SelectSqlBuilder<'a>) -> Self {
+		Cond {
+			node: CondNode::Exists {
+				select: Box::new(select),
+				not: false,
+			},
+		}
+	}
+
+	/// Condition `NOT EXISTS (SELECT ...)`
+	pub fn not_exists(select: SelectSqlBuilder<'a>) -> Self {
+		Cond {
+			node: CondNode::Exists {
+				select: Box::new(select),
+				not: true,
+			},
+		}
+	}
+
+	/// Group of conditions joined with `AND` (an empty group is `TRUE`).
+	pub fn all(conds: Vec<Cond<'a>>) -> Self {
+		Cond {
+			node: CondNode::And(conds),
+		}
+	}
+
+	/// Group of conditions joined with `OR` (an empty group is `FALSE`).
+	pub fn any(conds: Vec<Cond<'a>>) -> Self {
+		Cond {
+			node: CondNode::Or(conds),
+		}
+	}
+
+	/// Negate a condition, `NOT (...)`.
+	#[allow(clippy::should_implement_trait)]
+	pub fn not(cond: Cond<'a>) -> Self {
+		Cond {
+			node: CondNode::Not(Box::new(cond)),
+		}
+	}
+}
+
+impl<'a> From<WhereItem<'a>> for Cond<'a> {
+	fn from(item: WhereItem<'a>) -> Self {
+		Cond {
+			node: CondNode::Item(item),
+		}
+	}
+}
+// endregion: --- Constructors
+
+// region:    --- Combinators
+impl<'a> Cond<'a> {
+	/// Combine with `AND` (flatten if self is already an `AND` group).
+	pub fn and(self, other: Cond<'a>) -> Self {
+		match self.node {
+			CondNode::And(mut conds) => {
+				conds.push(other);
+				Cond::all(conds)
+			}
+			node => Cond::all(vec![Cond { node }, other]),
+		}
+	}
+
+	/// Combine with `OR` (flatten if self is already an `OR` group).
+	pub fn or(self, other: Cond<'a>) -> Self {
+		match self.node {
+			CondNode::Or(mut conds) => {
+				conds.push(other);
+				Cond::any(conds)
+			}
+			node => Cond::any(vec![Cond { node }, other]),
+		}
+	}
+}
+// endregion: --- Combinators
+
+// region:    --- Builder Utils
+impl<'a> Cond<'a> {
+	/// Render the condition, incrementing the `binding_idx` for each bound value.
+	pub(crate) fn sql(&self, dialect: Dialect, binding_idx: &mut usize) -> Result<String> {
+		let sql = match &self.node {
+			CondNode::Item(WhereItem { name, op, val }) => {
+				check_ident(name)?;
+				format!(
+					"{} {} {}",
+					x_column_name(dialect, name),
+					op,
+					sql_value(dialect, val.as_ref(), binding_idx)?
+				)
+			}
+			CondNode::In(WhereItem { name, op, val }) => {
+				check_ident(name)?;
+				// Note: The list is bound as one array, which only PostgreSQL supports.
+				if dialect != Dialect::Postgres {
+					return Err(Error::DialectUnsupported {
+						dialect,
+						feature: "= ANY (array)",
+					});
+				}
+				format!(
+					"{} {}({})",
+					x_column_name(dialect, name),
+					op,
+					sql_value(dialect, val.as_ref(), binding_idx)?
+				)
+			}
+			CondNode::Null { name, not } => {
+				check_ident(name)?;
+				let not = if *not { "NOT " } else { "" };
+				format!("{} IS {}NULL", x_column_name(dialect, name), not)
+			}
+			CondNode::EqNullable(WhereItem { name, val, .. }) if val.is_null() => {
+				check_ident(name)?;
+				format!("{} IS NULL", x_column_name(dialect, name))
+			}
+			CondNode::EqNullable(WhereItem { name, op, val }) => {
+				check_ident(name)?;
+				format!(
+					"{} {} {}",
+					x_column_name(dialect, name),
+					op,
+					sql_value(dialect, val.as_ref(), binding_idx)?
+				)
+			}
+			CondNode::Cols { left, op, right } => {
+				check_ident(left)?;
+				check_ident(right)?;
+				format!(
+					"{} {} {}",
+					x_column_name(dialect, left),
+					op,
+					x_column_name(dialect, right)
+				)
+			}
+			CondNode::Select { name, op, select } => {
+				check_ident(name)?;
+				let (next_idx, sql_select) = select.sql_at(dialect, *binding_idx)?;
+				*binding_idx = next_idx;
+				format!("{} {} ({})", x_column_name(dialect, name), op, sql_select.trim_end())
+			}
+			CondNode::Exists { select, not } => {
+				let (next_idx, sql_select) = select.sql_at(dialect, *binding_idx)?;
+				*binding_idx = next_idx;
+				let not = if *not { "NOT " } else { "" };
+				format!("{}EXISTS ({})", not, sql_select.trim_end())
+			}
+			CondNode::And(conds) => sql_group(dialect, conds, " AND ", "TRUE", binding_idx)?,
+			CondNode::Or(conds) => sql_group(dialect, conds, " OR ", "FALSE", binding_idx)?,
+			CondNode::Not(cond) => format!("NOT ({})", cond.sql(dialect, binding_idx)?),
+		};
+
+		Ok(sql)
+	}
+
+	/// Collect the values to bind, in the same order as the `sql(..)` rendering.
+	pub(crate) fn collect_vals<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>) {
+		match &self.node {
+			CondNode::Item(item) | CondNode::In(item) => vals.push(&item.val),
+			CondNode::Null { .. } | CondNode::Cols { .. } => (),
+			CondNode::EqNullable(item) => {
+				if !item.val.is_null() {
+					vals.push(&item.val)
+				}
+			}
+			CondNode::Select { select, .. } | CondNode::Exists { select, .. } => select.collect_vals(vals),
+			CondNode::And(conds) | CondNode::Or(conds) => conds.iter().for_each(|c| c.collect_vals(vals)),
+			CondNode::Not(cond) => cond.collect_vals(vals),
+		}
+	}
+}
+
+// SQL: ("a" = $1 OR "b" = $2)
+// Note: A single condition group is rendered without the parentheses.
+fn sql_group(dialect: Dialect, conds: &[Cond], sep: &str, empty: &str, binding_idx: &mut usize) -> Result<String> {
+	match conds {
+		[] => Ok(empty.to_string()),
+		[cond] => cond.sql(dialect, binding_idx),
+		conds => {
+			let parts = conds
+				.iter()
+				.map(|c| c.sql(dialect, binding_idx))
+				.collect::<Result<Vec<String>>>()?;
+			Ok(format!("({})", parts.join(sep)))
+		}
+	}
+}
+// endregion: --- Builder Utils
@@ -0,0 +1,148 @@
+//! `ON CONFLICT` clause for the `InsertSqlBuilder` (PostgreSQL upsert).
+
+use crate::core::{sql_value, sql_where_items, where_vals};
+use crate::utils::{check_ident, x_column_name};
+use crate::val::BoxedVal;
+use crate::{Cond, Dialect, Error, Field, Result};
+
+pub(crate) struct OnConflict<'a> {
+	pub target: ConflictTarget,
+	pub action: Option<ConflictAction<'a>>,
+}
+
+pub(crate) enum ConflictTarget {
+	/// No target (only valid with `DO NOTHING`)
+	None,
+	/// `ON CONFLICT ("col1", "col2")`
+	Columns(Vec<String>),
+	/// `ON CONFLICT ON CONSTRAINT "name"`
+	Constraint(String),
+}
+
+pub(crate) enum ConflictAction<'a> {
+	Nothing,
+	Update {
+		sets: Vec<ConflictSet<'a>>,
+		and_wheres: Vec<Cond<'a>>,
+	},
+}
+
+pub(crate) enum ConflictSet<'a> {
+	/// `"col" = $n`
+	Field(Field<'a>),
+	/// `"col" = EXCLUDED."col"`
+	Excluded(String),
+}
+
+impl<'a> OnConflict<'a> {
+	/// Return the update action, turning a `DO NOTHING` (or no action) into an empty `DO UPDATE`.
+	pub fn update_action(&mut self) -> (&mut Vec<ConflictSet<'a>>, &mut Vec<Cond<'a>>) {
+		if !matches!(self.action, Some(ConflictAction::Update { .. })) {
+			self.action = Some(ConflictAction::Update {
+				sets: Vec::new(),
+				and_wheres: Vec::new(),
+			});
+		}
+		match &mut self.action {
+			Some(ConflictAction::Update { sets, and_wheres }) => (sets, and_wheres),
+			_ => unreachable!("action set to update above"),
+		}
+	}
+
+	// SQL: ON CONFLICT ("id") DO UPDATE SET "title" = $3, "desc" = EXCLUDED."desc" WHERE "todo"."status" = $4
+	// Returns the next binding index and the sql.
+	pub fn sql(&self, dialect: Dialect, table: &str, idx_start: usize) -> Result<(usize, String)> {
+		let mut binding_idx = idx_start;
+
+		// Note: MySQL has `ON DUPLICATE KEY UPDATE` instead (not supported for now).
+		if dialect == Dialect::MySql {
+			return Err(Error::DialectUnsupported {
+				dialect,
+				feature: "ON CONFLICT",
+			});
+		}
+
+		// SQL: ON CONFLICT ("col1", ...)
+		let mut sql = String::from("ON CONFLICT ");
+		match &self.target {
+			ConflictTarget::None => (),
+			ConflictTarget::Columns(names) => {
+				for name in names {
+					check_ident(name)?;
+				}
+				let names = names
+					.iter()
+					.map(|n| x_column_name(dialect, n))
+					.collect::<Vec<String>>()
+					.join(", ");
+				sql.push_str(&format!("({}) ", names));
+			}
+			ConflictTarget::Constraint(name) => {
+				check_ident(name)?;
+				if dialect != Dialect::Postgres {
+					return Err(Error::DialectUnsupported {
+						dialect,
+						feature: "ON CONFLICT ON CONSTRAINT",
+					});
+				}
+				sql.push_str(&format!("ON CONSTRAINT {} ", x_column_name(dialect, name)));
+			}
+		}
+
+		match &self.action {
+			None => return Err(Error::OnConflictWithoutAction),
+			// SQL: DO NOTHING
+			Some(ConflictAction::Nothing) => sql.push_str("DO NOTHING "),
+			// SQL: DO UPDATE SET ...
+			Some(ConflictAction::Update { sets, and_wheres }) => {
+				if matches!(self.target, ConflictTarget::None) {
+					return Err(Error::OnConflictUpdateWithoutTarget);
+				}
+				if sets.is_empty() {
+					return Err(Error::EmptySet {
+						table: table.to_string(),
+					});
+				}
+
+				let sql_sets = sets
+					.iter()
+					.map(|set| {
+						let (name, value) = match set {
+							ConflictSet::Field(Field { name, value }) => {
+								(name, sql_value(dialect, value.as_ref(), &mut binding_idx)?)
+							}
+							ConflictSet::Excluded(name) => (name, format!("EXCLUDED.{}", x_column_name(dialect, name))),
+						};
+						check_ident(name)?;
+						Ok(format!("{} = {}", x_column_name(dialect, name), value))
+					})
+					.collect::<Result<Vec<String>>>()?
+					.join(", ");
+				sql.push_str(&format!("DO UPDATE SET {} ", sql_sets));
+
+				// SQL: WHERE ...
+				if !and_wheres.is_empty() {
+					let (next_idx, sql_where) = sql_where_items(dialect, and_wheres, binding_idx)?;
+					binding_idx = next_idx;
+					sql.push_str(&format!("WHERE {} ", sql_where));
+				}
+			}
+		}
+
+		Ok((binding_idx, sql))
+	}
+
+	/// Values to bind, in the `sql(..)` order.
+	pub fn vals(&self) -> Vec<&BoxedVal<'a>> {
+		let mut vals = Vec::new();
+		if let Some(ConflictAction::Update { sets, and_wheres }) = &self.action {
+			for set in sets {
+				if let ConflictSet::Field(field) = set {
+					vals.push(&field.value);
+				}
+			}
+			vals.extend(where_vals(and_wheres));
+		}
+		vals
+	}
+}
@@ -1,21 +1,12 @@
+use crate::cond::Cond;
+use crate::utils::{check_ident, x_column_name, x_expr, x_table_name};
+use crate::val::BoxedVal;
+use crate::val::SqlxBindable;
+use crate::{Dialect, Error, Result, SelectSqlBuilder, SqlxDatabase};
 use async_trait::async_trait;
-
-pub use crate::delete::delete;
-pub use crate::delete::delete_all;
-pub use crate::delete::DeleteSqlBuilder;
-pub use crate::insert::insert;
-pub use crate::insert::InsertSqlBuilder;
-pub use crate::select::select;
-pub use crate::select::SelectSqlBuilder;
-pub use crate::update::update;
-pub use crate::update::update_all;
-pub use crate::update::UpdateSqlBuilder;
-use crate::utils::x_column_name;
-pub use crate::val::SqlxBindable;
-pub use sqlb_macros::Fields;
+use futures_core::stream::BoxStream;
 use sqlx::Executor;
 use sqlx::FromRow;
-use sqlx::Postgres;
 
 #[derive(Debug)]
 pub struct Field<'a> {
@@ -72,78 +63,92 @@ impl<'a, T: 'a + SqlxBindable + Send + Sync> From<(&str, &'static str, T)> for W
 	}
 }
 
-#[derive(Clone)]
-pub(crate) struct OrderItem {
-	pub dir: OrderDir,
-	pub name: String,
-}
+#[async_trait]
+pub trait SqlBuilder<'a> {
+	/// Build the SQL statement for a dialect, or return an error if the builder is not in a valid state
+	/// (e.g., no table, update without data, guarded update/delete without where clause)
+	/// or uses something not supported by the dialect (e.g., `RETURNING` for MySQL).
+	fn try_sql_for(&self, dialect: Dialect) -> Result<String>;
 
-#[derive(Clone)]
-pub(crate) enum OrderDir {
-	Asc,
-	Desc,
-}
-
-impl From<&str> for OrderItem {
-	fn from(v: &str) -> Self {
-		if let Some(s) = v.strip_prefix('!') {
-			OrderItem {
-				dir: OrderDir::Desc,
-				name: x_column_name(s),
-			}
-		} else {
-			OrderItem {
-				dir: OrderDir::Asc,
-				name: x_column_name(v),
-			}
-		}
+	/// Build the PostgreSQL statement, or return an error if the builder is not in a valid state.
+	fn try_sql(&self) -> Result<String> {
+		self.try_sql_for(Dialect::Postgres)
 	}
-}
 
-impl From<&OrderItem> for String {
-	fn from(odr: &OrderItem) -> Self {
-		match odr.dir {
-			OrderDir::Asc => odr.name.to_string(),
-			OrderDir::Desc => format!("{} {}", odr.name, "DESC"),
+	/// Build the SQL statement for a dialect.
+	///
+	/// Panics if the builder is not in a valid state. Use `try_sql_for()` for the fallible version.
+	fn sql_for(&self, dialect: Dialect) -> String {
+		match self.try_sql_for(dialect) {
+			Ok(sql) => sql,
+			Err(ex) => panic!("FATAL - Cannot build sql. Cause: {}", ex),
 		}
 	}
-}
 
-#[async_trait]
-pub trait SqlBuilder<'a> {
-	fn sql(&self) -> String;
-	fn vals(&'a self) -> Box<dyn Iterator<Item = &Box<dyn SqlxBindable + 'a + Send + Sync>> + 'a + Send>;
+	/// Build the PostgreSQL statement.
+	///
+	/// Panics if the builder is not in a valid state. Use `try_sql()` for the fallible version.
+	/// Note: The `exec` and `fetch_...` functions always use `try_sql_for(..)` with the dialect of the database.
+	fn sql(&self) -> String {
+		self.sql_for(Dialect::Postgres)
+	}
+
+	fn vals(&'a self) -> Box<dyn Iterator<Item = &'a Box<dyn SqlxBindable + 'a + Send + Sync>> + 'a + Send>;
+
+	async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D>
+	where
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send;
 
-	async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D, sqlx::Error>
+	async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send;
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send;
 
-	async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>, sqlx::Error>
+	async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send;
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send;
 
-	async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>, sqlx::Error>
+	/// Fetch the rows as a `Stream` of D (rows are fetched as the stream is consumed).
+	fn fetch_stream<'e, DB, D>(&'a self, db_pool: DB) -> BoxStream<'e, Result<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send;
+		'a: 'e,
+		Self: Sync,
+		DB: Executor<'e> + 'e,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send + 'e;
 
-	async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64, sqlx::Error>
+	async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64>
 	where
-		DB: Executor<'q, Database = Postgres>;
+		DB: Executor<'q>,
+		DB::Database: SqlxDatabase;
 }
 
 pub trait Whereable<'a> {
 	fn and_where_eq<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self;
 	fn and_where<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, op: &'static str, val: T) -> Self;
+	fn and_where_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self;
+	fn and_where_not_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self;
+	fn and_where_null(self, name: &str) -> Self;
+	fn and_where_not_null(self, name: &str) -> Self;
+	fn and_where_eq_nullable<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self;
+	fn and_where_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self;
+	fn and_where_not_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self;
+	fn and_where_select(self, name: &str, op: &'static str, select: SelectSqlBuilder<'a>) -> Self;
+	fn and_where_exists(self, select: SelectSqlBuilder<'a>) -> Self;
+	fn and_where_not_exists(self, select: SelectSqlBuilder<'a>) -> Self;
+	fn and_where_cond(self, cond: Cond<'a>) -> Self;
 }
 
 // endregion: Common Types
 
 // region:    property into helpers
 pub(crate) fn add_to_where<'a, T: 'a + SqlxBindable + Send + Sync>(
-	and_wheres: &mut Vec<WhereItem<'a>>,
+	and_wheres: &mut Vec<Cond<'a>>,
 	name: &str,
 	op: &'static str,
 	val: T,
@@ -155,7 +160,15 @@ pub(crate) fn add_to_where<'a, T: 'a + SqlxBindable + Send + Sync>(
 		val: Box::new(val),
 	};
 
-	and_wheres.push(wher);
+	and_wheres.push(wher.into());
+}
+
+// Check that all of the names can be escaped (see `utils::check_ident`).
+pub(crate) fn check_idents<'n>(names: impl IntoIterator<Item = &'n str>) -> Result<()> {
+	for name in names {
+		check_ident(name)?;
+	}
+	Ok(())
 }
 
 // Note: for now does not care about the base.
@@ -167,47 +180,105 @@ pub(crate) fn into_returnings(_base: Option<Vec<String>>, names: &[&str]) -> Opt
 // region:    Builder Utils
 
 // SQL: "name1", "name2", ...
-pub(crate) fn sql_comma_names(fields: &[Field]) -> String {
+pub(crate) fn sql_comma_names(dialect: Dialect, fields: &[Field]) -> String {
 	fields
 		.iter()
-		.map(|Field { name, .. }| x_column_name(name))
+		.map(|Field { name, .. }| x_column_name(dialect, name))
 		.collect::<Vec<String>>()
 		.join(", ")
 }
 
-// SQL: $1, $2, $3, ...
-pub(crate) fn sql_comma_params(fields: &[Field]) -> (i32, String) {
+// If first array, idx_start should be 1
+// SQL: $1, $2, $3, ... (or ?, ?, ?, ...)
+// Returns the next binding index and the sql.
+pub(crate) fn sql_comma_params(dialect: Dialect, fields: &[Field], idx_start: usize) -> Result<(usize, String)> {
 	let mut vals = String::new();
-	let mut binding_idx = 1;
+	let mut binding_idx = idx_start;
 
 	for (idx, Field { value, .. }) in fields.iter().enumerate() {
 		if idx > 0 {
 			vals.push_str(", ");
 		};
-		match value.raw() {
-			None => {
-				vals.push_str(&format!("${}", binding_idx));
-				binding_idx += 1;
-			}
-			Some(raw) => vals.push_str(raw),
-		};
+		vals.push_str(&sql_value(dialect, value.as_ref(), &mut binding_idx)?);
 	}
-	(binding_idx, vals)
+	Ok((binding_idx, vals))
 }
 
-// If first array, idx_offset should be 1
-// SQL: "name1" = &1, ...
-pub(crate) fn sql_where_items(where_items: &[WhereItem], idx_start: usize) -> String {
-	where_items
+// SQL: $1 (or the sql of a `Raw` value, or the sql of an `Expr` value with its placeholders)
+// Increments the `binding_idx` for each bound value.
+pub(crate) fn sql_value(
+	dialect: Dialect,
+	value: &(dyn SqlxBindable + Send + Sync),
+	binding_idx: &mut usize,
+) -> Result<String> {
+	if value.is_default() && dialect == Dialect::Sqlite {
+		return Err(Error::DialectUnsupported {
+			dialect,
+			feature: "DEFAULT value",
+		});
+	}
+	if let Some(raw) = value.raw() {
+		return Ok(raw.to_string());
+	}
+	if let Some(expr) = value.expr() {
+		return x_expr(dialect, expr, value.expr_vals(), binding_idx);
+	}
+	let placeholder = dialect.placeholder(*binding_idx);
+	*binding_idx += 1;
+	Ok(placeholder)
+}
+
+// If first array, idx_start should be 1
+// SQL: "name1" = $1 AND ("name2" = $2 OR "name3" = $3) ...
+// Returns the next binding index and the sql.
+pub(crate) fn sql_where_items(dialect: Dialect, and_wheres: &[Cond], idx_start: usize) -> Result<(usize, String)> {
+	let mut binding_idx = idx_start;
+	let sql = and_wheres
 		.iter()
-		.enumerate()
-		.map(|(idx, WhereItem { name, op, .. })| format!("{} {} ${}", x_column_name(name), op, idx + idx_start))
-		.collect::<Vec<String>>()
-		.join(" AND ")
+		.map(|cond| cond.sql(dialect, &mut binding_idx))
+		.collect::<Result<Vec<String>>>()?
+		.join(" AND ");
+	Ok((binding_idx, sql))
+}
+
+// Values to bind for the where conditions (same order as `sql_where_items`).
+pub(crate) fn where_vals<'s, 'a>(and_wheres: &'s [Cond<'a>]) -> Vec<&'s BoxedVal<'a>> {
+	let mut vals = Vec::new();
+	for cond in and_wheres {
+		cond.collect_vals(&mut vals);
+	}
+	vals
 }
 
 // SQL: "Id", "userName", ...
-pub(crate) fn sql_returnings(returnings: &[String]) -> String {
-	returnings.iter().map(|r| x_column_name(r)).collect::<Vec<String>>().join(", ")
+// Returns an error if the dialect does not support `RETURNING`.
+pub(crate) fn sql_returnings(dialect: Dialect, returnings: &[String]) -> Result<String> {
+	if !dialect.supports_returning() {
+		return Err(Error::DialectUnsupported {
+			dialect,
+			feature: "RETURNING",
+		});
+	}
+	Ok(returnings
+		.iter()
+		.map(|r| x_column_name(dialect, r))
+		.collect::<Vec<String>>()
+		.join(", "))
+}
+
+// SQL: "staging" AS "s", "other"
+// For the `UPDATE ... FROM` and `DELETE ... USING` tables (table name with an optional alias).
+pub(crate) fn sql_tables(dialect: Dialect, tables: &[(String, Option<String>)]) -> Result<String> {
+	let mut parts = Vec::with_capacity(tables.len());
+	for (table, alias) in tables {
+		check_idents([table.as_str()])?;
+		check_idents(alias.as_deref())?;
+		let part = match alias {
+			Some(alias) => format!("{} AS {}", x_table_name(dialect, table), x_table_name(dialect, alias)),
+			None => x_table_name(dialect, table),
+		};
+		parts.push(part);
+	}
+	Ok(parts.join(", "))
 }
 // endregion: Builder Utils
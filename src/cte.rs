@@ -0,0 +1,106 @@
+use crate::core::check_idents;
+use crate::utils::x_table_name;
+use crate::val::BoxedVal;
+use crate::{DeleteSqlBuilder, Dialect, InsertSqlBuilder, Result, SelectSqlBuilder, UpdateSqlBuilder};
+
+/// The query of a common table expression (see the builders `.with(..)` / `.with_recursive(..)`).
+///
+/// Any of the builders can be a CTE query (converted with `.into()`), e.g., a `delete().returning(..)` for a
+/// data-modifying CTE.
+pub enum CteQuery<'a> {
+	Select(Box<SelectSqlBuilder<'a>>),
+	Insert(Box<InsertSqlBuilder<'a>>),
+	Update(Box<UpdateSqlBuilder<'a>>),
+	Delete(Box<DeleteSqlBuilder<'a>>),
+}
+
+impl<'a> From<SelectSqlBuilder<'a>> for CteQuery<'a> {
+	fn from(sb: SelectSqlBuilder<'a>) -> Self {
+		CteQuery::Select(Box::new(sb))
+	}
+}
+
+impl<'a> From<InsertSqlBuilder<'a>> for CteQuery<'a> {
+	fn from(sb: InsertSqlBuilder<'a>) -> Self {
+		CteQuery::Insert(Box::new(sb))
+	}
+}
+
+impl<'a> From<UpdateSqlBuilder<'a>> for CteQuery<'a> {
+	fn from(sb: UpdateSqlBuilder<'a>) -> Self {
+		CteQuery::Update(Box::new(sb))
+	}
+}
+
+impl<'a> From<DeleteSqlBuilder<'a>> for CteQuery<'a> {
+	fn from(sb: DeleteSqlBuilder<'a>) -> Self {
+		CteQuery::Delete(Box::new(sb))
+	}
+}
+
+impl<'a> CteQuery<'a> {
+	fn sql_at(&self, dialect: Dialect, idx_start: usize) -> Result<(usize, String)> {
+		match self {
+			CteQuery::Select(sb) => sb.sql_at(dialect, idx_start),
+			CteQuery::Insert(sb) => sb.sql_at(dialect, idx_start),
+			CteQuery::Update(sb) => sb.sql_at(dialect, idx_start),
+			CteQuery::Delete(sb) => sb.sql_at(dialect, idx_start),
+		}
+	}
+
+	fn collect_vals<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>) {
+		match self {
+			CteQuery::Select(sb) => sb.collect_vals(vals),
+			CteQuery::Insert(sb) => sb.collect_vals(vals),
+			CteQuery::Update(sb) => sb.collect_vals(vals),
+			CteQuery::Delete(sb) => sb.collect_vals(vals),
+		}
+	}
+}
+
+/// The `WITH` clause of a builder (empty by default).
+#[derive(Default)]
+pub(crate) struct Ctes<'a> {
+	/// `WITH RECURSIVE` (applies to the whole clause).
+	recursive: bool,
+	items: Vec<(String, CteQuery<'a>)>,
+}
+
+impl<'a> Ctes<'a> {
+	pub(crate) fn push(&mut self, name: &str, query: CteQuery<'a>, recursive: bool) {
+		self.recursive = self.recursive || recursive;
+		self.items.push((name.to_string(), query));
+	}
+
+	pub(crate) fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	// SQL: WITH "name1" AS (...), "name2" AS (...)
+	// Returns the next binding index and the sql (empty if no CTE).
+	pub(crate) fn sql_at(&self, dialect: Dialect, idx_start: usize) -> Result<(usize, String)> {
+		if self.items.is_empty() {
+			return Ok((idx_start, String::new()));
+		}
+
+		check_idents(self.items.iter().map(|(name, _)| name.as_str()))?;
+
+		let mut binding_idx = idx_start;
+		let mut sql_items = Vec::with_capacity(self.items.len());
+		for (name, query) in self.items.iter() {
+			let (next_idx, sql_query) = query.sql_at(dialect, binding_idx)?;
+			binding_idx = next_idx;
+			sql_items.push(format!("{} AS ({})", x_table_name(dialect, name), sql_query.trim_end()));
+		}
+
+		let recursive = if self.recursive { "RECURSIVE " } else { "" };
+		Ok((binding_idx, format!("WITH {}{} ", recursive, sql_items.join(", "))))
+	}
+
+	/// Collect the values to bind, in the same order as the `sql_at(..)` rendering.
+	pub(crate) fn collect_vals<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>) {
+		for (_, query) in self.items.iter() {
+			query.collect_vals(vals);
+		}
+	}
+}
@@ -1,14 +1,21 @@
-use crate::core::{add_to_where, into_returnings, sql_returnings, sql_where_items};
-use crate::core::{WhereItem, Whereable};
+use crate::core::Whereable;
+use crate::core::{
+	add_to_where, check_idents, into_returnings, sql_returnings, sql_tables, sql_where_items, where_vals,
+};
+use crate::cte::{CteQuery, Ctes};
 use crate::utils::x_table_name;
-use crate::{sqlx_exec, SqlBuilder, SqlxBindable};
+use crate::val::BoxedVal;
+use crate::{sqlx_exec, Cond, Dialect, Error, Result, SelectSqlBuilder, SqlBuilder, SqlxBindable, SqlxDatabase};
 use async_trait::async_trait;
-use sqlx::{Executor, FromRow, Postgres};
+use futures_core::stream::BoxStream;
+use sqlx::{Executor, FromRow};
 
 pub fn delete<'a>() -> DeleteSqlBuilder<'a> {
 	DeleteSqlBuilder {
+		ctes: Ctes::default(),
 		guard_all: true,
 		table: None,
+		usings: Vec::new(),
 		returnings: None,
 		and_wheres: Vec::new(),
 	}
@@ -16,25 +23,57 @@ pub fn delete<'a>() -> DeleteSqlBuilder<'a> {
 
 pub fn delete_all<'a>() -> DeleteSqlBuilder<'a> {
 	DeleteSqlBuilder {
+		ctes: Ctes::default(),
 		guard_all: false,
 		table: None,
+		usings: Vec::new(),
 		returnings: None,
 		and_wheres: Vec::new(),
 	}
 }
 
 pub struct DeleteSqlBuilder<'a> {
+	ctes: Ctes<'a>,
 	guard_all: bool,
 	table: Option<String>,
+	/// The `USING` tables, with their optional alias.
+	usings: Vec<(String, Option<String>)>,
 	returnings: Option<Vec<String>>,
-	and_wheres: Vec<WhereItem<'a>>,
+	and_wheres: Vec<Cond<'a>>,
 }
 
 impl<'a> DeleteSqlBuilder<'a> {
+	/// Add a common table expression, `WITH "name" AS (...)`, with any builder as the query.
+	pub fn with(mut self, name: &str, query: impl Into<CteQuery<'a>>) -> Self {
+		self.ctes.push(name, query.into(), false);
+		self
+	}
+
+	/// Same as `with(..)`, but renders the `WITH` clause as `WITH RECURSIVE`.
+	pub fn with_recursive(mut self, name: &str, query: impl Into<CteQuery<'a>>) -> Self {
+		self.ctes.push(name, query.into(), true);
+		self
+	}
+
 	pub fn table(mut self, table: &str) -> Self {
 		self.table = Some(table.to_string());
 		self
 	}
+
+	/// Add a table to the `DELETE FROM ... USING "table"`.
+	/// Note: `USING` is PostgreSQL only (`Error::DialectUnsupported` for SQLite and MySQL).
+	/// Note: Use `Cond::cols_eq(..)` to join it with the deleted table (e.g., `Cond::cols_eq("todo.project_id", "project.id")`).
+	pub fn using(mut self, table: &str) -> Self {
+		self.usings.push((table.to_string(), None));
+		self
+	}
+
+	/// Same as `using(..)`, with an alias, `USING "table" AS "alias"`.
+	pub fn using_as(mut self, table: &str, alias: &str) -> Self {
+		self.usings.push((table.to_string(), Some(alias.to_string())));
+		self
+	}
+
 	pub fn and_where<T: 'a + SqlxBindable + Send + Sync>(mut self, name: &str, op: &'static str, val: T) -> Self {
 		add_to_where(&mut self.and_wheres, name, op, val);
 		self
@@ -45,41 +84,113 @@ impl<'a> DeleteSqlBuilder<'a> {
 		self
 	}
 
+	/// Add a `name = ANY($n)` condition, with the values bound as one array (e.g., `Vec<i64>` or `&[i64]`).
+	pub fn and_where_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		self.and_where_cond(Cond::is_in(name, vals))
+	}
+
+	/// Add a `name <> ALL($n)` condition (i.e., `NOT IN`), with the values bound as one array.
+	pub fn and_where_not_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		self.and_where_cond(Cond::not_in(name, vals))
+	}
+
+	/// Add a `name IS NULL` condition.
+	pub fn and_where_null(self, name: &str) -> Self {
+		self.and_where_cond(Cond::is_null(name))
+	}
+
+	/// Add a `name IS NOT NULL` condition.
+	pub fn and_where_not_null(self, name: &str) -> Self {
+		self.and_where_cond(Cond::is_not_null(name))
+	}
+
+	/// Add a `name = $n` condition, rendered as `name IS NULL` when the value is null (e.g., `None`).
+	/// Note: `and_where_eq` always renders `= $n`, which never matches with a NULL.
+	pub fn and_where_eq_nullable<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		self.and_where_cond(Cond::eq_nullable(name, val))
+	}
+
+	/// Add a `name IS DISTINCT FROM $n` condition (null-safe `<>`).
+	pub fn and_where_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		self.and_where_cond(Cond::distinct_from(name, val))
+	}
+
+	/// Add a `name IS NOT DISTINCT FROM $n` condition (null-safe `=`).
+	pub fn and_where_not_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		self.and_where_cond(Cond::not_distinct_from(name, val))
+	}
+
+	/// Add a `name op (SELECT ...)` condition (e.g., `.and_where_select("project_id", "IN", sub_select)`).
+	pub fn and_where_select(self, name: &str, op: &'static str, select: SelectSqlBuilder<'a>) -> Self {
+		self.and_where_cond(Cond::op_select(name, op, select))
+	}
+
+	/// Add a `EXISTS (SELECT ...)` condition.
+	pub fn and_where_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.and_where_cond(Cond::exists(select))
+	}
+
+	/// Add a `NOT EXISTS (SELECT ...)` condition.
+	pub fn and_where_not_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.and_where_cond(Cond::not_exists(select))
+	}
+
+	/// Add a condition tree (see `Cond`) to the where clause (joined with `AND` with the other conditions).
+	pub fn and_where_cond(mut self, cond: Cond<'a>) -> Self {
+		self.and_wheres.push(cond);
+		self
+	}
+
 	pub fn returning(mut self, names: &[&str]) -> Self {
 		self.returnings = into_returnings(self.returnings, names);
 		self
 	}
 
-	pub async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64, sqlx::Error>
+	pub async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64>
 	where
-		DB: Executor<'q, Database = Postgres>,
+		DB: Executor<'q>,
+		DB::Database: SqlxDatabase,
 	{
 		sqlx_exec::exec(db_pool, self).await
 	}
 
-	pub async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D, sqlx::Error>
+	pub async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		sqlx_exec::fetch_as_one::<DB, D, _>(db_pool, self).await
 	}
 
-	pub async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>, sqlx::Error>
+	pub async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		sqlx_exec::fetch_as_optional::<DB, D, _>(db_pool, self).await
 	}
 
-	pub async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>, sqlx::Error>
+	pub async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		sqlx_exec::fetch_as_all::<DB, D, _>(db_pool, self).await
 	}
+
+	/// Fetch the rows as a `Stream` of D (rows are fetched as the stream is consumed).
+	pub fn fetch_stream<'e, DB, D>(&'a self, db_pool: DB) -> BoxStream<'e, Result<D>>
+	where
+		'a: 'e,
+		DB: Executor<'e> + 'e,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send + 'e,
+	{
+		sqlx_exec::fetch_stream::<DB, D, _>(db_pool, self)
+	}
 }
 
 impl<'a> Whereable<'a> for DeleteSqlBuilder<'a> {
@@ -90,70 +201,169 @@ impl<'a> Whereable<'a> for DeleteSqlBuilder<'a> {
 	fn and_where<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, op: &'static str, val: T) -> Self {
 		DeleteSqlBuilder::and_where(self, name, op, val)
 	}
+
+	fn and_where_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		DeleteSqlBuilder::and_where_in(self, name, vals)
+	}
+
+	fn and_where_not_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		DeleteSqlBuilder::and_where_not_in(self, name, vals)
+	}
+
+	fn and_where_null(self, name: &str) -> Self {
+		DeleteSqlBuilder::and_where_null(self, name)
+	}
+
+	fn and_where_not_null(self, name: &str) -> Self {
+		DeleteSqlBuilder::and_where_not_null(self, name)
+	}
+
+	fn and_where_eq_nullable<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		DeleteSqlBuilder::and_where_eq_nullable(self, name, val)
+	}
+
+	fn and_where_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		DeleteSqlBuilder::and_where_distinct_from(self, name, val)
+	}
+
+	fn and_where_not_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		DeleteSqlBuilder::and_where_not_distinct_from(self, name, val)
+	}
+
+	fn and_where_select(self, name: &str, op: &'static str, select: SelectSqlBuilder<'a>) -> Self {
+		DeleteSqlBuilder::and_where_select(self, name, op, select)
+	}
+
+	fn and_where_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		DeleteSqlBuilder::and_where_exists(self, select)
+	}
+
+	fn and_where_not_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		DeleteSqlBuilder::and_where_not_exists(self, select)
+	}
+
+	fn and_where_cond(self, cond: Cond<'a>) -> Self {
+		DeleteSqlBuilder::and_where_cond(self, cond)
+	}
 }
 
-#[async_trait]
-impl<'a> SqlBuilder<'a> for DeleteSqlBuilder<'a> {
-	fn sql(&self) -> String {
+// region:    --- Builder Utils
+impl<'a> DeleteSqlBuilder<'a> {
+	/// Render the delete with the placeholders starting at `idx_start` (e.g., as a CTE query).
+	/// Returns the next binding index and the sql.
+	pub(crate) fn sql_at(&self, dialect: Dialect, idx_start: usize) -> Result<(usize, String)> {
 		// SQL: DELETE FROM table_name WHERE w1 = $1, ... RETURNING r1, r2, ..;
 
+		let table = self.table.as_ref().ok_or(Error::MissingTable)?;
+
+		// -- Check the builder state
+		if self.guard_all && self.and_wheres.is_empty() {
+			return Err(Error::UnguardedDelete {
+				table: table.to_string(),
+			});
+		}
+
+		// -- Check names
+		check_idents([table.as_str()])?;
+		check_idents(self.returnings.iter().flatten().map(|r| r.as_str()))?;
+
+		// SQL: WITH "name" AS (...)
+		let (mut binding_idx, mut sql) = self.ctes.sql_at(dialect, idx_start)?;
+
 		// SQL: DELETE FROM table_name
-		let mut sql = String::from("DELETE FROM ");
+		sql.push_str("DELETE FROM ");
+		sql.push_str(&format!("{} ", x_table_name(dialect, table)));
 
-		if let Some(table) = &self.table {
-			sql.push_str(&x_table_name(table));
+		// SQL: USING "project" AS "p", ...
+		if !self.usings.is_empty() {
+			if dialect != Dialect::Postgres {
+				return Err(Error::DialectUnsupported {
+					dialect,
+					feature: "DELETE ... USING",
+				});
+			}
+			sql.push_str(&format!("USING {} ", sql_tables(dialect, &self.usings)?));
 		}
 
 		// SQL: WHERE w1 < $1, ...
 		if !self.and_wheres.is_empty() {
-			let sql_where = sql_where_items(&self.and_wheres, 1);
+			let (next_idx, sql_where) = sql_where_items(dialect, &self.and_wheres, binding_idx)?;
+			binding_idx = next_idx;
 			sql.push_str(&format!("WHERE {} ", &sql_where));
-		} else if self.guard_all {
-			// For now panic, will return error later
-			panic!("FATAL - Trying to call a delete without any where clause. If needed, use sqlb::delete_all(table_name). ")
 		}
 
 		// SQL: RETURNING "r1", "r2", ...
 		if let Some(returnings) = &self.returnings {
-			sql.push_str(&format!("RETURNING {} ", sql_returnings(returnings)));
+			sql.push_str(&format!("RETURNING {} ", sql_returnings(dialect, returnings)?));
 		}
 
-		sql
+		Ok((binding_idx, sql))
+	}
+
+	/// Collect the values to bind, in the same order as the `sql_at(..)` rendering.
+	pub(crate) fn collect_vals<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>) {
+		self.ctes.collect_vals(vals);
+		vals.extend(where_vals(&self.and_wheres));
+	}
+}
+// endregion: --- Builder Utils
+
+#[async_trait]
+impl<'a> SqlBuilder<'a> for DeleteSqlBuilder<'a> {
+	fn try_sql_for(&self, dialect: Dialect) -> Result<String> {
+		let (_, sql) = self.sql_at(dialect, 1)?;
+		Ok(sql)
 	}
 
-	fn vals(&'a self) -> Box<dyn Iterator<Item = &Box<dyn SqlxBindable + 'a + Send + Sync>> + 'a + Send> {
-		let iter = self.and_wheres.iter().map(|wi| &wi.val);
-		Box::new(iter)
+	fn vals(&'a self) -> Box<dyn Iterator<Item = &'a Box<dyn SqlxBindable + 'a + Send + Sync>> + 'a + Send> {
+		let mut vals = Vec::new();
+		self.collect_vals(&mut vals);
+		Box::new(vals.into_iter())
 	}
 
-	async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64, sqlx::Error>
+	async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64>
 	where
-		DB: Executor<'q, Database = Postgres>,
+		DB: Executor<'q>,
+		DB::Database: SqlxDatabase,
 	{
 		Self::exec(self, db_pool).await
 	}
 
-	async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D, sqlx::Error>
+	async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_one::<DB, D>(self, db_pool).await
 	}
 
-	async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>, sqlx::Error>
+	async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_optional::<DB, D>(self, db_pool).await
 	}
 
-	async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>, sqlx::Error>
+	async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_all::<DB, D>(self, db_pool).await
 	}
+
+	fn fetch_stream<'e, DB, D>(&'a self, db_pool: DB) -> BoxStream<'e, Result<D>>
+	where
+		'a: 'e,
+		Self: Sync,
+		DB: Executor<'e> + 'e,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send + 'e,
+	{
+		Self::fetch_stream::<DB, D>(self, db_pool)
+	}
 }
@@ -0,0 +1,218 @@
+//! SQL dialects (placeholders, identifier quoting, RETURNING and LIMIT support), and the `SqlxDatabase`
+//! trait binding the builder values for each supported sqlx database.
+//!
+//! The builders are database agnostic, the SQL is rendered for a dialect with `sql_for(..)`,
+//! and the `exec` / `fetch_...` functions use the dialect of the sqlx database of the executor.
+//!
+//! - PostgreSQL is always available (and the `sql()` default).
+//! - SQLite and MySQL are behind the `sqlite` and `mysql` cargo features.
+//!
+//! ```
+//! use sqlb::{Dialect, SqlBuilder};
+//! let sb = sqlb::select().table("todo").and_where_eq("id", 1).offset(10);
+//! assert_eq!(sb.sql(), r#"SELECT * FROM "todo" WHERE "id" = $1 OFFSET 10 "#);
+//! assert_eq!(sb.sql_for(Dialect::Sqlite), r#"SELECT * FROM "todo" WHERE "id" = ? LIMIT -1 OFFSET 10 "#);
+//! assert_eq!(sb.sql_for(Dialect::MySql), "SELECT * FROM `todo` WHERE `id` = ? LIMIT 18446744073709551615 OFFSET 10 ");
+//! ```
+
+use crate::keyset::{encode_cursor, row_cursor_vals};
+use crate::{Result, SqlxBindable};
+use sqlx::query::Query;
+use sqlx::IntoArguments;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+	Postgres,
+	Sqlite,
+	MySql,
+}
+
+impl Dialect {
+	/// Bind parameter placeholder for the binding index (1 based), `$1` for PostgreSQL and `?` otherwise.
+	pub fn placeholder(&self, idx: usize) -> String {
+		match self {
+			Dialect::Postgres => format!("${}", idx),
+			Dialect::Sqlite | Dialect::MySql => "?".to_string(),
+		}
+	}
+
+	/// Quote one identifier part, `"name"` (or `` `name` `` for MySQL, with the inner backticks doubled).
+	pub fn quote_ident(&self, part: &str) -> String {
+		match self {
+			Dialect::Postgres | Dialect::Sqlite => format!("\"{}\"", part),
+			Dialect::MySql => format!("`{}`", part.replace('`', "``")),
+		}
+	}
+
+	/// True if `INSERT/UPDATE/DELETE ... RETURNING` is supported (SQLite since 3.35).
+	pub fn supports_returning(&self) -> bool {
+		match self {
+			Dialect::Postgres | Dialect::Sqlite => true,
+			Dialect::MySql => false,
+		}
+	}
+
+	/// Maximum number of bind parameters for one statement.
+	pub fn max_bind_params(&self) -> usize {
+		match self {
+			// The count is sent as a 16-bit integer in the wire protocol
+			Dialect::Postgres | Dialect::MySql => u16::MAX as usize,
+			// SQLITE_MAX_VARIABLE_NUMBER default since SQLite 3.32
+			Dialect::Sqlite => 32766,
+		}
+	}
+
+	// SQL: LIMIT 10 OFFSET 20
+	// Note: SQLite and MySQL require a LIMIT for an OFFSET, so the "no limit" value is used.
+	pub(crate) fn sql_limit_offset(&self, limit: Option<i64>, offset: Option<i64>) -> String {
+		let limit = match (limit, offset, self) {
+			(Some(limit), _, _) => Some(limit.to_string()),
+			(None, Some(_), Dialect::Sqlite) => Some("-1".to_string()),
+			(None, Some(_), Dialect::MySql) => Some(u64::MAX.to_string()),
+			(None, _, _) => None,
+		};
+
+		let mut sql = String::new();
+		if let Some(limit) = limit {
+			sql.push_str(&format!("LIMIT {} ", limit));
+		}
+		if let Some(offset) = offset {
+			sql.push_str(&format!("OFFSET {} ", offset));
+		}
+		sql
+	}
+}
+
+// region:    --- SqlxDatabase
+
+/// A sqlx database supported by the builders, with its SQL dialect and the binding of the `SqlxBindable` values.
+pub trait SqlxDatabase: sqlx::Database {
+	const DIALECT: Dialect;
+
+	/// The query arguments type (e.g., `PgArguments`).
+	type Args<'q>: IntoArguments<'q, Self> + Send + 'q;
+
+	fn query(sql: &str) -> Query<'_, Self, Self::Args<'_>>;
+
+	fn bind_val<'q>(
+		val: &'q dyn SqlxBindable,
+		query: Query<'q, Self, Self::Args<'q>>,
+	) -> Query<'q, Self, Self::Args<'q>>;
+
+	fn rows_affected(result: &Self::QueryResult) -> u64;
+
+	/// Encode the keyset pagination cursor from the values of the `names` columns of the row (see `KeysetPage`).
+	fn row_cursor(row: &Self::Row, names: &[&str]) -> Result<String>;
+
+	/// Get the `i64` value of the `name` column of the row (e.g., a count).
+	fn row_i64(row: &Self::Row, name: &str) -> Result<i64>;
+
+	/// Get the `bool` value of the `name` column of the row (e.g., an `EXISTS`).
+	fn row_bool(row: &Self::Row, name: &str) -> Result<bool>;
+}
+
+impl SqlxDatabase for sqlx::Postgres {
+	const DIALECT: Dialect = Dialect::Postgres;
+
+	type Args<'q> = sqlx::postgres::PgArguments;
+
+	fn query(sql: &str) -> Query<'_, Self, Self::Args<'_>> {
+		sqlx::query(sql)
+	}
+
+	fn bind_val<'q>(
+		val: &'q dyn SqlxBindable,
+		query: Query<'q, Self, Self::Args<'q>>,
+	) -> Query<'q, Self, Self::Args<'q>> {
+		val.bind_query(query)
+	}
+
+	fn rows_affected(result: &Self::QueryResult) -> u64 {
+		result.rows_affected()
+	}
+
+	fn row_cursor(row: &Self::Row, names: &[&str]) -> Result<String> {
+		let vals = row_cursor_vals!(row, names)?;
+		Ok(encode_cursor(&vals))
+	}
+
+	fn row_i64(row: &Self::Row, name: &str) -> Result<i64> {
+		Ok(sqlx::Row::try_get(row, name)?)
+	}
+
+	fn row_bool(row: &Self::Row, name: &str) -> Result<bool> {
+		Ok(sqlx::Row::try_get(row, name)?)
+	}
+}
+
+#[cfg(feature = "sqlite")]
+impl SqlxDatabase for sqlx::Sqlite {
+	const DIALECT: Dialect = Dialect::Sqlite;
+
+	type Args<'q> = sqlx::sqlite::SqliteArguments<'q>;
+
+	fn query(sql: &str) -> Query<'_, Self, Self::Args<'_>> {
+		sqlx::query(sql)
+	}
+
+	fn bind_val<'q>(
+		val: &'q dyn SqlxBindable,
+		query: Query<'q, Self, Self::Args<'q>>,
+	) -> Query<'q, Self, Self::Args<'q>> {
+		val.bind_query_sqlite(query)
+	}
+
+	fn rows_affected(result: &Self::QueryResult) -> u64 {
+		result.rows_affected()
+	}
+
+	fn row_cursor(row: &Self::Row, names: &[&str]) -> Result<String> {
+		let vals = row_cursor_vals!(row, names)?;
+		Ok(encode_cursor(&vals))
+	}
+
+	fn row_i64(row: &Self::Row, name: &str) -> Result<i64> {
+		Ok(sqlx::Row::try_get(row, name)?)
+	}
+
+	fn row_bool(row: &Self::Row, name: &str) -> Result<bool> {
+		Ok(sqlx::Row::try_get(row, name)?)
+	}
+}
+
+#[cfg(feature = "mysql")]
+impl SqlxDatabase for sqlx::MySql {
+	const DIALECT: Dialect = Dialect::MySql;
+
+	type Args<'q> = sqlx::mysql::MySqlArguments;
+
+	fn query(sql: &str) -> Query<'_, Self, Self::Args<'_>> {
+		sqlx::query(sql)
+	}
+
+	fn bind_val<'q>(
+		val: &'q dyn SqlxBindable,
+		query: Query<'q, Self, Self::Args<'q>>,
+	) -> Query<'q, Self, Self::Args<'q>> {
+		val.bind_query_mysql(query)
+	}
+
+	fn rows_affected(result: &Self::QueryResult) -> u64 {
+		result.rows_affected()
+	}
+
+	fn row_cursor(row: &Self::Row, names: &[&str]) -> Result<String> {
+		let vals = row_cursor_vals!(row, names)?;
+		Ok(encode_cursor(&vals))
+	}
+
+	fn row_i64(row: &Self::Row, name: &str) -> Result<i64> {
+		Ok(sqlx::Row::try_get(row, name)?)
+	}
+
+	fn row_bool(row: &Self::Row, name: &str) -> Result<bool> {
+		Ok(sqlx::Row::try_get(row, name)?)
+	}
+}
+
+// endregion: --- SqlxDatabase
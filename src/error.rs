@@ -0,0 +1,150 @@
+use crate::Dialect;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+	// -- Builder
+	/// The builder was executed without a `.table(..)`.
+	MissingTable,
+
+	/// Update without any data, would render an empty `SET`.
+	EmptySet {
+		table: String,
+	},
+
+	/// Update without any where clause on a guarded builder (use `sqlb::update_all()` if intended).
+	UnguardedUpdate {
+		table: String,
+	},
+
+	/// Delete without any where clause on a guarded builder (use `sqlb::delete_all()` if intended).
+	UnguardedDelete {
+		table: String,
+	},
+
+	/// Insert with an empty `.data_rows(..)`.
+	InsertNoRows,
+
+	/// Insert row (index in the rows) without the same columns as the first row.
+	InsertRowMismatch {
+		row_idx: usize,
+	},
+
+	/// Insert select (see `.data_select(..)`) without any column name,
+	/// or with a number of column names different from the select columns.
+	InsertSelectColumnMismatch {
+		names: usize,
+		columns: usize,
+	},
+
+	/// Insert with an `ON CONFLICT` target without `.do_nothing()` or `.do_update...(..)`.
+	OnConflictWithoutAction,
+
+	/// Insert with an `ON CONFLICT DO UPDATE` without a conflict target (required by PostgreSQL).
+	OnConflictUpdateWithoutTarget,
+
+	/// Inner, left, right, or full join without any `ON` condition.
+	JoinWithoutOn {
+		table: String,
+	},
+
+	/// Cross join with some `ON` conditions.
+	CrossJoinWithOn {
+		table: String,
+	},
+
+	/// SQL expression with a number of `{}` different from the number of bound values.
+	ExprBindMismatch {
+		expr: String,
+		expected: usize,
+		actual: usize,
+	},
+
+	/// Keyset pagination without `order_bys`, or with an order by expression (only columns are supported).
+	KeysetInvalidOrder,
+
+	/// Keyset pagination cursor that cannot be decoded, or with a number of values different from the `order_bys`.
+	InvalidCursor,
+
+	/// Keyset pagination sort-key column with a NULL, or with a type that cannot be in a cursor
+	/// (supported: integers, floats, bool, text, uuid, and timestamptz).
+	KeysetColumnUnsupported {
+		name: String,
+	},
+
+	/// Offset pagination (see `fetch_page(..)`) with a `page` or `page_size` below 1,
+	/// or with a `(page - 1) * page_size` offset above `i64::MAX`.
+	InvalidPage {
+		page: i64,
+		page_size: i64,
+	},
+
+	/// Select with a `DISTINCT ON` column that is not one of the leading `ORDER BY` columns (required by PostgreSQL).
+	DistinctOnOrderMismatch {
+		name: String,
+	},
+
+	/// Select with `.lock_of(..)`, `.skip_locked()`, or `.nowait()` without a lock strength (e.g., `.for_update()`).
+	LockWithoutStrength,
+
+	/// Select with a row lock (e.g., `.for_update()`) and compound branches (e.g., `.union(..)`),
+	/// or with a compound branch having a row lock (rejected by PostgreSQL).
+	LockWithCompound,
+
+	/// Update with `.with_version(..)` and a data field (or data column) setting the version column.
+	VersionInData {
+		name: String,
+	},
+
+	/// Update with `.with_version(..)` that did not update any row
+	/// (i.e., the row was updated or deleted since the expected version was read).
+	StaleVersion {
+		table: String,
+	},
+
+	/// Number of bind parameters above what the database supports for one statement.
+	TooManyBindParams {
+		count: usize,
+		max: usize,
+	},
+
+	/// Table or column name that cannot be safely escaped.
+	InvalidIdentifier {
+		name: String,
+	},
+
+	/// SQL construct not supported by the dialect (e.g., `RETURNING` for MySQL).
+	DialectUnsupported {
+		dialect: Dialect,
+		feature: &'static str,
+	},
+
+	// -- Externals
+	Sqlx(sqlx::Error),
+}
+
+// region:    --- Froms
+impl From<sqlx::Error> for Error {
+	fn from(val: sqlx::Error) -> Self {
+		Error::Sqlx(val)
+	}
+}
+// endregion: --- Froms
+
+// region:    --- Error Boilerplate
+impl core::fmt::Display for Error {
+	fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+		write!(fmt, "{self:?}")
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Error::Sqlx(ex) => Some(ex),
+			_ => None,
+		}
+	}
+}
+// endregion: --- Error Boilerplate
@@ -1,133 +1,485 @@
-use crate::core::{into_returnings, sql_comma_names, sql_comma_params, sql_returnings};
-use crate::utils::x_table_name;
-use crate::{sqlx_exec, Field, SqlBuilder, SqlxBindable};
+use crate::conflict::{ConflictAction, ConflictSet, ConflictTarget, OnConflict};
+use crate::core::{add_to_where, check_idents, into_returnings, sql_comma_names, sql_comma_params, sql_returnings};
+use crate::cte::{CteQuery, Ctes};
+use crate::sqlx_exec::bind_vals;
+use crate::utils::{x_column_name, x_table_name};
+use crate::val::BoxedVal;
+use crate::{
+	sqlx_exec, Cond, Dialect, Error, Field, HasFields, Result, SelectSqlBuilder, SqlBuilder, SqlxBindable, SqlxDatabase,
+};
 use async_trait::async_trait;
-use sqlx::{Executor, FromRow, Postgres};
+use futures_core::stream::BoxStream;
+use sqlx::{Acquire, Executor, FromRow};
 
 pub fn insert<'a>() -> InsertSqlBuilder<'a> {
 	InsertSqlBuilder {
+		ctes: Ctes::default(),
 		table: None,
-		data: Vec::new(),
+		rows: vec![Vec::new()],
+		data_select: None,
+		on_conflict: None,
 		returnings: None,
 	}
 }
 
 // #[derive(Clone)]
 pub struct InsertSqlBuilder<'a> {
+	ctes: Ctes<'a>,
 	table: Option<String>,
-	data: Vec<Field<'a>>,
+	/// One `Vec<Field>` per row to insert (`.data(..)` is one row).
+	rows: Vec<Vec<Field<'a>>>,
+	/// The column names and select of `.data_select(..)` (the `rows` are then ignored).
+	data_select: Option<(Vec<String>, Box<SelectSqlBuilder<'a>>)>,
+	on_conflict: Option<OnConflict<'a>>,
 	returnings: Option<Vec<String>>,
 }
 
 impl<'a> InsertSqlBuilder<'a> {
+	/// Add a common table expression, `WITH "name" AS (...)`, with any builder as the query.
+	pub fn with(mut self, name: &str, query: impl Into<CteQuery<'a>>) -> Self {
+		self.ctes.push(name, query.into(), false);
+		self
+	}
+
+	/// Same as `with(..)`, but renders the `WITH` clause as `WITH RECURSIVE`.
+	pub fn with_recursive(mut self, name: &str, query: impl Into<CteQuery<'a>>) -> Self {
+		self.ctes.push(name, query.into(), true);
+		self
+	}
+
 	pub fn table(mut self, table: &str) -> Self {
 		self.table = Some(table.to_string());
 		self
 	}
 
 	pub fn data(mut self, fields: Vec<Field<'a>>) -> Self {
-		self.data = fields;
+		self.rows = vec![fields];
+		self.data_select = None;
+		self
+	}
+
+	/// Set multiple rows to insert, rendered as `VALUES (...), (...), ...`.
+	/// All rows must have the same columns, in the same order.
+	///
+	/// Note: `exec(..)` / `fetch_...(..)` execute one statement, and return `Error::TooManyBindParams` above the max bind
+	/// params of the database (see `Dialect::max_bind_params()`). For large row counts, use `exec_chunked(..)`
+	/// or `fetch_all_chunked(..)`, which split the rows into several statements in one transaction.
+	pub fn data_rows(mut self, rows: Vec<Vec<Field<'a>>>) -> Self {
+		self.rows = rows;
+		self.data_select = None;
+		self
+	}
+
+	/// Set multiple rows to insert from `HasFields` items (uses `.all_fields()` so that all rows have the same columns).
+	pub fn data_rows_from<T: HasFields>(self, items: impl IntoIterator<Item = T>) -> Self {
+		let rows = items.into_iter().map(|item| item.all_fields()).collect();
+		self.data_rows(rows)
+	}
+
+	/// Insert the rows of a select, `INSERT INTO "table" ("name1", ...) SELECT ...` (instead of the `.data(..)` values).
+	/// The select must have the same number of columns as the names (when known, i.e., not `*`).
+	///
+	/// e.g., `sqlb::insert().table("todo_archive").data_select(&["id", "title"], sqlb::select().table("todo").columns(&["id", "title"]))`
+	///
+	/// Note: With an `on_conflict(..)`, SQLite requires the select to have a where clause (`Error::DialectUnsupported`).
+	pub fn data_select(mut self, names: &[&str], select: SelectSqlBuilder<'a>) -> Self {
+		self.rows = Vec::new();
+		self.data_select = Some((names.iter().map(|s| s.to_string()).collect(), Box::new(select)));
+		self
+	}
+
+	/// Set the conflict target columns, `ON CONFLICT ("col1", ...)`.
+	/// Must be followed by `.do_nothing()` or `.do_update...(..)`.
+	pub fn on_conflict(mut self, names: &[&str]) -> Self {
+		self.on_conflict_mut().target = ConflictTarget::Columns(names.iter().map(|s| s.to_string()).collect());
+		self
+	}
+
+	/// Set the conflict target constraint, `ON CONFLICT ON CONSTRAINT "name"`.
+	pub fn on_conflict_constraint(mut self, name: &str) -> Self {
+		self.on_conflict_mut().target = ConflictTarget::Constraint(name.to_string());
+		self
+	}
+
+	/// `ON CONFLICT ... DO NOTHING` (the target is optional).
+	pub fn do_nothing(mut self) -> Self {
+		self.on_conflict_mut().action = Some(ConflictAction::Nothing);
+		self
+	}
+
+	/// `ON CONFLICT ... DO UPDATE SET "col" = $n, ...` (can be combined with `do_update_excluded`).
+	pub fn do_update(mut self, fields: Vec<Field<'a>>) -> Self {
+		let (sets, _) = self.on_conflict_mut().update_action();
+		sets.extend(fields.into_iter().map(ConflictSet::Field));
+		self
+	}
+
+	/// `ON CONFLICT ... DO UPDATE SET "col" = EXCLUDED."col", ...` (i.e., the value proposed for insertion).
+	pub fn do_update_excluded(mut self, names: &[&str]) -> Self {
+		let (sets, _) = self.on_conflict_mut().update_action();
+		sets.extend(names.iter().map(|n| ConflictSet::Excluded(n.to_string())));
+		self
+	}
+
+	/// Add a where condition to the `DO UPDATE` (only rows matching will be updated).
+	/// Note: Use the table name to qualify the existing row columns (e.g., `todo.status`), and `excluded.` for the proposed ones.
+	pub fn do_update_and_where<T: 'a + SqlxBindable + Send + Sync>(
+		mut self,
+		name: &str,
+		op: &'static str,
+		val: T,
+	) -> Self {
+		let (_, and_wheres) = self.on_conflict_mut().update_action();
+		add_to_where(and_wheres, name, op, val);
 		self
 	}
 
+	/// Add a where condition tree (see `Cond`) to the `DO UPDATE`.
+	pub fn do_update_and_where_cond(mut self, cond: Cond<'a>) -> Self {
+		let (_, and_wheres) = self.on_conflict_mut().update_action();
+		and_wheres.push(cond);
+		self
+	}
+
+	fn on_conflict_mut(&mut self) -> &mut OnConflict<'a> {
+		self.on_conflict.get_or_insert_with(|| OnConflict {
+			target: ConflictTarget::None,
+			action: None,
+		})
+	}
+
 	pub fn returning(mut self, names: &[&str]) -> Self {
 		self.returnings = into_returnings(self.returnings, names);
 		self
 	}
 
-	pub async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64, sqlx::Error>
+	pub async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64>
 	where
-		DB: Executor<'q, Database = Postgres>,
+		DB: Executor<'q>,
+		DB::Database: SqlxDatabase,
 	{
 		sqlx_exec::exec(db_pool, self).await
 	}
 
-	pub async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D, sqlx::Error>
+	pub async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		sqlx_exec::fetch_as_one::<DB, D, _>(db_pool, self).await
 	}
 
-	pub async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>, sqlx::Error>
+	pub async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		sqlx_exec::fetch_as_optional::<DB, D, _>(db_pool, self).await
 	}
 
-	pub async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>, sqlx::Error>
+	pub async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		sqlx_exec::fetch_as_all::<DB, D, _>(db_pool, self).await
 	}
+
+	/// Fetch the rows as a `Stream` of D (rows are fetched as the stream is consumed).
+	pub fn fetch_stream<'e, DB, D>(&'a self, db_pool: DB) -> BoxStream<'e, Result<D>>
+	where
+		'a: 'e,
+		DB: Executor<'e> + 'e,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send + 'e,
+	{
+		sqlx_exec::fetch_stream::<DB, D, _>(db_pool, self)
+	}
+
+	/// Execute the insert in one or more statements so that each statement stays under the max bind params of the database
+	/// (see `Dialect::max_bind_params()`).
+	/// All of the statements are executed in one transaction (a savepoint if `db` is already a transaction).
+	///
+	/// Note: `exec(..)` executes one statement and returns `Error::TooManyBindParams` if above the limit.
+	/// Note: The `with(..)` CTEs are part of each statement (avoid data-modifying CTEs).
+	pub async fn exec_chunked<'c, A>(&'a self, db: A) -> Result<u64>
+	where
+		A: Acquire<'c>,
+		A::Database: SqlxDatabase,
+		for<'t> &'t mut <A::Database as sqlx::Database>::Connection: Executor<'t, Database = A::Database>,
+	{
+		let dialect = <A::Database as SqlxDatabase>::DIALECT;
+		let mut tx = db.begin().await?;
+
+		let mut count = 0;
+		for rows in self.row_chunks(dialect) {
+			let (_, sql) = self.sql_for_rows(dialect, rows, 1)?;
+			let query = bind_vals::<A::Database>(&sql, self.chunk_vals(rows))?;
+			let result = query.execute(&mut *tx).await?;
+			count += <A::Database as SqlxDatabase>::rows_affected(&result);
+		}
+
+		tx.commit().await?;
+
+		Ok(count)
+	}
+
+	/// Same as `exec_chunked(..)`, but returns the rows (typically used with `.returning(..)`).
+	pub async fn fetch_all_chunked<'c, A, D>(&'a self, db: A) -> Result<Vec<D>>
+	where
+		A: Acquire<'c>,
+		A::Database: SqlxDatabase,
+		for<'t> &'t mut <A::Database as sqlx::Database>::Connection: Executor<'t, Database = A::Database>,
+		D: for<'r> FromRow<'r, <A::Database as sqlx::Database>::Row> + Unpin + Send,
+	{
+		let dialect = <A::Database as SqlxDatabase>::DIALECT;
+		let mut tx = db.begin().await?;
+
+		let mut items = Vec::new();
+		for rows in self.row_chunks(dialect) {
+			let (_, sql) = self.sql_for_rows(dialect, rows, 1)?;
+			let query = bind_vals::<A::Database>(&sql, self.chunk_vals(rows))?;
+			let query = query.try_map(|row| D::from_row(&row));
+			items.extend(query.fetch_all(&mut *tx).await?);
+		}
+
+		tx.commit().await?;
+
+		Ok(items)
+	}
 }
 
-#[async_trait]
-impl<'a> SqlBuilder<'a> for InsertSqlBuilder<'a> {
-	fn sql(&self) -> String {
-		// SQL: INSERT INTO table_name (name1, ...) VALUES ($1, ...) RETURNING r1, ...;
+// region:    --- Builder Utils
+impl<'a> InsertSqlBuilder<'a> {
+	/// Render the insert with the placeholders starting at `idx_start` (e.g., as a CTE query).
+	/// Returns the next binding index and the sql.
+	pub(crate) fn sql_at(&self, dialect: Dialect, idx_start: usize) -> Result<(usize, String)> {
+		self.sql_for_rows(dialect, &self.rows, idx_start)
+	}
+
+	/// Collect the values to bind, in the same order as the `sql_at(..)` rendering.
+	pub(crate) fn collect_vals<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>) {
+		vals.extend(self.chunk_vals(&self.rows));
+	}
+
+	fn sql_for_rows(&self, dialect: Dialect, rows: &[Vec<Field<'a>>], idx_start: usize) -> Result<(usize, String)> {
+		// SQL: INSERT INTO table_name (name1, ...) VALUES ($1, ...), ($n, ...) RETURNING r1, ...;
+		//   or INSERT INTO table_name (name1, ...) SELECT ... RETURNING r1, ...;
+
+		let table = self.table.as_ref().ok_or(Error::MissingTable)?;
+
+		// -- Check names
+		check_idents([table.as_str()])?;
+		check_idents(self.returnings.iter().flatten().map(|r| r.as_str()))?;
+
+		// SQL: WITH "name" AS (...)
+		let (mut binding_idx, mut sql) = self.ctes.sql_at(dialect, idx_start)?;
 
 		// SQL: INSERT INTO table_name
-		let mut sql = String::from("INSERT INTO ");
+		sql.push_str("INSERT INTO ");
+		sql.push_str(&format!("{} ", x_table_name(dialect, table)));
 
-		if let Some(table) = &self.table {
-			sql.push_str(&x_table_name(table));
-		}
+		match &self.data_select {
+			Some((names, select)) => {
+				// -- Check the column names against the select columns
+				let columns = select.column_count();
+				if names.is_empty() || columns.is_some_and(|columns| columns != names.len()) {
+					return Err(Error::InsertSelectColumnMismatch {
+						names: names.len(),
+						columns: columns.unwrap_or(0),
+					});
+				}
+				check_idents(names.iter().map(|n| n.as_str()))?;
+
+				// Note: SQLite parses the `ON` of `SELECT ... FROM "t" ON CONFLICT` as a join constraint,
+				//       so the select must have a `WHERE` (e.g., `.and_where_cond(Cond::all(vec![]))` for `WHERE TRUE`).
+				if dialect == Dialect::Sqlite && self.on_conflict.is_some() && !select.has_where() {
+					return Err(Error::DialectUnsupported {
+						dialect,
+						feature: "INSERT ... SELECT ... ON CONFLICT without a WHERE",
+					});
+				}
 
-		// NotDB: empty data is a valid usecase, if the row has a all required field with default or auto gen.
-		let fields = &self.data;
-		// SQL: (name1, name2, ...)
-		sql.push_str(&format!("({}) ", sql_comma_names(fields)));
+				// SQL: (name1, name2, ...) SELECT ...
+				let (next_idx, sql_select) = select.sql_at(dialect, binding_idx)?;
+				binding_idx = next_idx;
+				let names: Vec<String> = names.iter().map(|n| x_column_name(dialect, n)).collect();
+				sql.push_str(&format!("({}) {}", names.join(", "), sql_select));
+			}
+			None => {
+				let first_row = rows.first().ok_or(Error::InsertNoRows)?;
 
-		// SQL: VALUES ($1, $2, ...)
-		sql.push_str(&format!("VALUES ({}) ", sql_comma_params(fields).1));
+				// -- Check that all rows have the same columns as the first one
+				for (row_idx, row) in rows.iter().enumerate().skip(1) {
+					let same_names =
+						row.len() == first_row.len() && row.iter().zip(first_row).all(|(a, b)| a.name == b.name);
+					if !same_names {
+						return Err(Error::InsertRowMismatch { row_idx });
+					}
+				}
+				check_idents(first_row.iter().map(|f| f.name.as_str()))?;
+
+				// Note: Empty data is a valid use case, if all the required fields have a default or are auto generated.
+				// SQL: DEFAULT VALUES (MySQL: () VALUES (), ...)
+				if first_row.is_empty() && dialect != Dialect::MySql {
+					if rows.len() > 1 {
+						return Err(Error::DialectUnsupported {
+							dialect,
+							feature: "multiple DEFAULT VALUES rows",
+						});
+					}
+					sql.push_str("DEFAULT VALUES ");
+				} else {
+					// SQL: (name1, name2, ...)
+					sql.push_str(&format!("({}) ", sql_comma_names(dialect, first_row)));
+
+					// SQL: VALUES ($1, $2, ...), ($3, $4, ...)
+					let mut sql_rows = Vec::with_capacity(rows.len());
+					for row in rows {
+						let (next_idx, sql_row) = sql_comma_params(dialect, row, binding_idx)?;
+						binding_idx = next_idx;
+						sql_rows.push(format!("({})", sql_row));
+					}
+					sql.push_str(&format!("VALUES {} ", sql_rows.join(", ")));
+				}
+			}
+		}
+
+		// SQL: ON CONFLICT ("col1") DO UPDATE SET ...
+		if let Some(on_conflict) = &self.on_conflict {
+			let (next_idx, sql_on_conflict) = on_conflict.sql(dialect, table, binding_idx)?;
+			binding_idx = next_idx;
+			sql.push_str(&sql_on_conflict);
+		}
 
 		// SQL: RETURNING "r1", "r2", ...
 		if let Some(returnings) = &self.returnings {
-			sql.push_str(&format!("RETURNING {} ", sql_returnings(returnings)));
+			sql.push_str(&format!("RETURNING {} ", sql_returnings(dialect, returnings)?));
 		}
 
-		sql
+		Ok((binding_idx, sql))
 	}
 
-	fn vals(&'a self) -> Box<dyn Iterator<Item = &Box<dyn SqlxBindable + 'a + Send + Sync>> + 'a + Send> {
-		let iter = self.data.iter().map(|field| &field.value);
-		Box::new(iter)
+	/// Split the rows so that the bound values of each chunk stay under the max bind params of the dialect.
+	fn row_chunks(&self, dialect: Dialect) -> Vec<&[Vec<Field<'a>>]> {
+		let max = dialect.max_bind_params();
+		let mut chunks = Vec::new();
+		let mut start = 0;
+		// Note: The CTE, select, and on conflict values are part of each chunk statement.
+		let stmt_count: usize = self
+			.cte_vals()
+			.into_iter()
+			.chain(self.select_vals())
+			.chain(self.conflict_vals())
+			.map(|val| val.bind_count())
+			.sum();
+		let mut count = stmt_count;
+
+		for (idx, row) in self.rows.iter().enumerate() {
+			let row_count: usize = row.iter().map(|f| f.value.bind_count()).sum();
+			if count + row_count > max && idx > start {
+				chunks.push(&self.rows[start..idx]);
+				start = idx;
+				count = stmt_count;
+			}
+			count += row_count;
+		}
+		chunks.push(&self.rows[start..]);
+
+		chunks
+	}
+
+	/// Values to bind for a chunk of rows (i.e., the CTE, select or rows, and on conflict values).
+	fn chunk_vals<'s>(&'s self, rows: &'s [Vec<Field<'a>>]) -> impl Iterator<Item = &'s BoxedVal<'a>> + Send {
+		self.cte_vals()
+			.into_iter()
+			.chain(self.select_vals())
+			.chain(rows_vals(rows))
+			.chain(self.conflict_vals())
+	}
+
+	fn select_vals(&self) -> Vec<&BoxedVal<'a>> {
+		let mut vals = Vec::new();
+		if let Some((_, select)) = &self.data_select {
+			select.collect_vals(&mut vals);
+		}
+		vals
+	}
+
+	fn cte_vals(&self) -> Vec<&BoxedVal<'a>> {
+		let mut vals = Vec::new();
+		self.ctes.collect_vals(&mut vals);
+		vals
+	}
+
+	fn conflict_vals(&self) -> impl Iterator<Item = &BoxedVal<'a>> + Send {
+		self.on_conflict.iter().flat_map(|c| c.vals())
+	}
+}
+
+// Values to bind for the rows, in the `sql_for_rows` order.
+fn rows_vals<'s, 'a>(rows: &'s [Vec<Field<'a>>]) -> impl Iterator<Item = &'s BoxedVal<'a>> {
+	rows.iter().flat_map(|row| row.iter().map(|field| &field.value))
+}
+// endregion: --- Builder Utils
+
+#[async_trait]
+impl<'a> SqlBuilder<'a> for InsertSqlBuilder<'a> {
+	fn try_sql_for(&self, dialect: Dialect) -> Result<String> {
+		let (_, sql) = self.sql_at(dialect, 1)?;
+		Ok(sql)
 	}
 
-	async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64, sqlx::Error>
+	fn vals(&'a self) -> Box<dyn Iterator<Item = &'a Box<dyn SqlxBindable + 'a + Send + Sync>> + 'a + Send> {
+		Box::new(self.chunk_vals(&self.rows))
+	}
+
+	async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64>
 	where
-		DB: Executor<'q, Database = Postgres>,
+		DB: Executor<'q>,
+		DB::Database: SqlxDatabase,
 	{
 		Self::exec(self, db_pool).await
 	}
 
-	async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D, sqlx::Error>
+	async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_one::<DB, D>(self, db_pool).await
 	}
 
-	async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>, sqlx::Error>
+	async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_optional::<DB, D>(self, db_pool).await
 	}
 
-	async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>, sqlx::Error>
+	async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_all::<DB, D>(self, db_pool).await
 	}
+
+	fn fetch_stream<'e, DB, D>(&'a self, db_pool: DB) -> BoxStream<'e, Result<D>>
+	where
+		'a: 'e,
+		Self: Sync,
+		DB: Executor<'e> + 'e,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send + 'e,
+	{
+		Self::fetch_stream::<DB, D>(self, db_pool)
+	}
 }
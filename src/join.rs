@@ -0,0 +1,168 @@
+use crate::core::{check_idents, sql_value, WhereItem};
+use crate::utils::{x_column_name, x_table_name};
+use crate::val::BoxedVal;
+use crate::{Dialect, Error, Result, SqlxBindable};
+
+/// Create a join specification for the `SelectSqlBuilder` `..._join(..)` functions.
+///
+/// e.g., `sqlb::join("project").alias("p").on_cols_eq("p.id", "todo.project_id")`
+pub fn join<'a>(table: &str) -> Join<'a> {
+	Join {
+		table: table.to_string(),
+		alias: None,
+		ons: Vec::new(),
+	}
+}
+
+pub struct Join<'a> {
+	table: String,
+	alias: Option<String>,
+	ons: Vec<OnItem<'a>>,
+}
+
+/// One `ON` condition of a join (all conditions are joined with `AND`).
+pub(crate) enum OnItem<'a> {
+	/// Column to column condition, e.g., `"p"."id" = "t"."project_id"` (nothing bound).
+	Cols {
+		left: String,
+		op: &'static str,
+		right: String,
+	},
+	/// Column to value condition, e.g., `"p"."name" = $1`
+	Val(WhereItem<'a>),
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum JoinKind {
+	Inner,
+	Left,
+	Right,
+	Full,
+	Cross,
+}
+
+impl JoinKind {
+	fn sql(&self) -> &'static str {
+		match self {
+			JoinKind::Inner => "INNER JOIN",
+			JoinKind::Left => "LEFT JOIN",
+			JoinKind::Right => "RIGHT JOIN",
+			JoinKind::Full => "FULL JOIN",
+			JoinKind::Cross => "CROSS JOIN",
+		}
+	}
+}
+
+impl<'a> Join<'a> {
+	pub fn alias(mut self, alias: &str) -> Self {
+		self.alias = Some(alias.to_string());
+		self
+	}
+
+	/// Add a column to column condition (e.g., `("p.id", "=", "t.project_id")`).
+	pub fn on_cols(mut self, left: &str, op: &'static str, right: &str) -> Self {
+		self.ons.push(OnItem::Cols {
+			left: left.to_string(),
+			op,
+			right: right.to_string(),
+		});
+		self
+	}
+
+	pub fn on_cols_eq(self, left: &str, right: &str) -> Self {
+		self.on_cols(left, "=", right)
+	}
+
+	/// Add a column to bound value condition (e.g., `("p.name", "=", "Project A")`).
+	pub fn on<T: 'a + SqlxBindable + Send + Sync>(mut self, name: &str, op: &'static str, val: T) -> Self {
+		self.ons.push(OnItem::Val((name, op, val).into()));
+		self
+	}
+
+	pub fn on_eq<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		self.on(name, "=", val)
+	}
+}
+
+/// A join added to a builder (the join spec with its kind).
+pub(crate) struct JoinItem<'a> {
+	pub kind: JoinKind,
+	pub join: Join<'a>,
+}
+
+impl<'a> JoinItem<'a> {
+	pub fn vals(&self) -> impl Iterator<Item = &BoxedVal<'a>> {
+		self.join.ons.iter().filter_map(|on| match on {
+			OnItem::Val(wi) => Some(&wi.val),
+			OnItem::Cols { .. } => None,
+		})
+	}
+}
+
+// region:    --- Builder Utils
+
+// SQL: INNER JOIN "project" AS "p" ON "p"."id" = "t"."project_id" AND "p"."name" = $1 ...
+// Returns the next binding index and the sql.
+pub(crate) fn sql_joins(dialect: Dialect, joins: &[JoinItem], idx_start: usize) -> Result<(usize, String)> {
+	let mut binding_idx = idx_start;
+	let mut sql = String::new();
+
+	for JoinItem { kind, join } in joins {
+		// -- Check the join spec
+		check_idents(std::iter::once(join.table.as_str()).chain(join.alias.as_deref()))?;
+		for on in join.ons.iter() {
+			match on {
+				OnItem::Cols { left, right, .. } => check_idents([left.as_str(), right.as_str()])?,
+				OnItem::Val(wi) => check_idents([wi.name.as_str()])?,
+			}
+		}
+		match (kind, join.ons.is_empty()) {
+			(JoinKind::Cross, false) => {
+				return Err(Error::CrossJoinWithOn {
+					table: join.table.clone(),
+				})
+			}
+			(JoinKind::Cross, true) => (),
+			(_, true) => {
+				return Err(Error::JoinWithoutOn {
+					table: join.table.clone(),
+				})
+			}
+			(_, false) => (),
+		}
+
+		// SQL: INNER JOIN "project"
+		sql.push_str(&format!("{} {} ", kind.sql(), x_table_name(dialect, &join.table)));
+
+		// SQL: AS "p"
+		if let Some(alias) = &join.alias {
+			sql.push_str(&format!("AS {} ", x_table_name(dialect, alias)));
+		}
+
+		// SQL: ON "p"."id" = "t"."project_id" AND ...
+		if !join.ons.is_empty() {
+			let sql_ons = join
+				.ons
+				.iter()
+				.map(|on| match on {
+					OnItem::Cols { left, op, right } => Ok(format!(
+						"{} {} {}",
+						x_column_name(dialect, left),
+						op,
+						x_column_name(dialect, right)
+					)),
+					OnItem::Val(WhereItem { name, op, val }) => {
+						let value = sql_value(dialect, val.as_ref(), &mut binding_idx)?;
+						Ok(format!("{} {} {}", x_column_name(dialect, name), op, value))
+					}
+				})
+				.collect::<Result<Vec<String>>>()?
+				.join(" AND ");
+			sql.push_str(&format!("ON {} ", sql_ons));
+		}
+	}
+
+	Ok((binding_idx, sql))
+}
+
+// endregion: --- Builder Utils
@@ -0,0 +1,212 @@
+//! Keyset (cursor) pagination for the `SelectSqlBuilder` (see `after_cursor` and `fetch_keyset`).
+//!
+//! The cursor is the last row values of the `order_bys` columns, encoded as an opaque (url safe) string,
+//! and rendered as the `("a", "b") > ($1, $2)` row comparison predicate (or the expanded form for mixed ASC/DESC).
+
+use crate::order::OrderBy;
+use crate::utils::x_column_name;
+use crate::val::BoxedVal;
+use crate::{Dialect, Error, Result, SqlxBindable};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// A page of keyset pagination, with the cursor for the next page
+/// (None when the page has less rows than the select limit, i.e., the last page).
+#[derive(Debug)]
+pub struct KeysetPage<D> {
+	pub items: Vec<D>,
+	pub next_cursor: Option<String>,
+}
+
+/// Sort-key value of a cursor.
+#[derive(Debug)]
+pub(crate) enum CursorVal {
+	Int(i64),
+	Float(f64),
+	Bool(bool),
+	Text(String),
+	Uuid(Uuid),
+	Time(OffsetDateTime),
+}
+
+impl CursorVal {
+	fn into_bindable<'a>(self) -> Box<dyn SqlxBindable + 'a + Send + Sync> {
+		match self {
+			CursorVal::Int(v) => Box::new(v),
+			CursorVal::Float(v) => Box::new(v),
+			CursorVal::Bool(v) => Box::new(v),
+			CursorVal::Text(v) => Box::new(v),
+			CursorVal::Uuid(v) => Box::new(v),
+			CursorVal::Time(v) => Box::new(v),
+		}
+	}
+}
+
+/// Read the cursor values of the `names` columns of a row, trying the supported types in order.
+/// Returns `Error::KeysetColumnUnsupported` for a NULL or a value of another type.
+///
+/// Note: A macro, so that the `try_get` are for the concrete row type of each `SqlxDatabase` implementation.
+macro_rules! row_cursor_vals {
+	($row:expr, $names:expr) => {{
+		use crate::keyset::CursorVal;
+		use sqlx::Row;
+		$names
+			.iter()
+			.map(|name| {
+				let name = *name;
+				let val = if let Ok(Some(v)) = $row.try_get::<Option<i64>, _>(name) {
+					CursorVal::Int(v)
+				} else if let Ok(Some(v)) = $row.try_get::<Option<i32>, _>(name) {
+					CursorVal::Int(v as i64)
+				} else if let Ok(Some(v)) = $row.try_get::<Option<i16>, _>(name) {
+					CursorVal::Int(v as i64)
+				} else if let Ok(Some(v)) = $row.try_get::<Option<f64>, _>(name) {
+					CursorVal::Float(v)
+				} else if let Ok(Some(v)) = $row.try_get::<Option<f32>, _>(name) {
+					CursorVal::Float(v as f64)
+				} else if let Ok(Some(v)) = $row.try_get::<Option<String>, _>(name) {
+					CursorVal::Text(v)
+				} else if let Ok(Some(v)) = $row.try_get::<Option<uuid::Uuid>, _>(name) {
+					CursorVal::Uuid(v)
+				} else if let Ok(Some(v)) = $row.try_get::<Option<time::OffsetDateTime>, _>(name) {
+					CursorVal::Time(v)
+				} else if let Ok(Some(v)) = $row.try_get::<Option<bool>, _>(name) {
+					CursorVal::Bool(v)
+				} else {
+					return Err(crate::Error::KeysetColumnUnsupported { name: name.to_string() });
+				};
+				Ok(val)
+			})
+			.collect::<crate::Result<Vec<CursorVal>>>()
+	}};
+}
+pub(crate) use row_cursor_vals;
+
+// Format: `<tag><payload len>:<payload>` for each value, base64 url safe encoded.
+pub(crate) fn encode_cursor(vals: &[CursorVal]) -> String {
+	let mut buf = String::new();
+	for val in vals {
+		let (tag, payload) = match val {
+			CursorVal::Int(v) => ('i', v.to_string()),
+			CursorVal::Float(v) => ('f', v.to_string()),
+			CursorVal::Bool(v) => ('b', (*v as u8).to_string()),
+			CursorVal::Text(v) => ('s', v.to_string()),
+			CursorVal::Uuid(v) => ('u', v.to_string()),
+			CursorVal::Time(v) => ('t', v.unix_timestamp_nanos().to_string()),
+		};
+		buf.push_str(&format!("{}{}:{}", tag, payload.len(), payload));
+	}
+	URL_SAFE_NO_PAD.encode(buf)
+}
+
+/// Decode a cursor from `encode_cursor`, None if not a valid cursor.
+pub(crate) fn decode_cursor(cursor: &str) -> Option<Vec<CursorVal>> {
+	let buf = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+	let mut rest = buf.as_str();
+	let mut vals = Vec::new();
+	while !rest.is_empty() {
+		let tag = rest.chars().next()?;
+		let (len, after) = rest[tag.len_utf8()..].split_once(':')?;
+		let len: usize = len.parse().ok()?;
+		let payload = after.get(..len)?;
+		rest = &after[len..];
+
+		let val = match tag {
+			'i' => CursorVal::Int(payload.parse().ok()?),
+			'f' => CursorVal::Float(payload.parse().ok()?),
+			'b' => CursorVal::Bool(payload == "1"),
+			's' => CursorVal::Text(payload.to_string()),
+			'u' => CursorVal::Uuid(payload.parse().ok()?),
+			't' => CursorVal::Time(OffsetDateTime::from_unix_timestamp_nanos(payload.parse().ok()?).ok()?),
+			_ => return None,
+		};
+		vals.push(val);
+	}
+	Some(vals)
+}
+
+/// The decoded cursor of `SelectSqlBuilder::after_cursor(..)` (the decoding error is returned when building the sql).
+pub(crate) enum KeysetCursor<'a> {
+	Invalid,
+	Vals(Vec<Box<dyn SqlxBindable + 'a + Send + Sync>>),
+}
+
+impl<'a> KeysetCursor<'a> {
+	pub fn new(cursor: &str) -> Self {
+		match decode_cursor(cursor) {
+			Some(vals) => KeysetCursor::Vals(vals.into_iter().map(CursorVal::into_bindable).collect()),
+			None => KeysetCursor::Invalid,
+		}
+	}
+
+	// SQL: ("a", "b") > ($1, $2)
+	//   or, for mixed ASC/DESC: ("a" > $1 OR ("a" = $2 AND "b" < $3))
+	// Note: The values are bound as many times as they appear (see `collect_vals`).
+	pub fn sql(&self, dialect: Dialect, order_bys: &[OrderBy], binding_idx: &mut usize) -> Result<String> {
+		let names = keyset_names(order_bys)?;
+		let count = match self {
+			KeysetCursor::Vals(vals) if vals.len() == names.len() => vals.len(),
+			_ => return Err(Error::InvalidCursor),
+		};
+
+		let names: Vec<String> = names.iter().map(|n| x_column_name(dialect, n)).collect();
+		let ops: Vec<&str> = order_bys.iter().map(|o| if o.is_desc() { "<" } else { ">" }).collect();
+		let mut next_placeholder = || {
+			let placeholder = dialect.placeholder(*binding_idx);
+			*binding_idx += 1;
+			placeholder
+		};
+
+		// -- Same direction for all, row comparison
+		if ops.iter().all(|op| *op == ops[0]) {
+			let placeholders: Vec<String> = (0..count).map(|_| next_placeholder()).collect();
+			return Ok(format!(
+				"({}) {} ({})",
+				names.join(", "),
+				ops[0],
+				placeholders.join(", ")
+			));
+		}
+
+		// -- Mixed directions, expanded as `a > $1 OR (a = $2 AND b < $3) ...`
+		let mut ors = Vec::with_capacity(count);
+		for idx in 0..count {
+			let mut ands: Vec<String> = (0..idx).map(|j| format!("{} = {}", names[j], next_placeholder())).collect();
+			ands.push(format!("{} {} {}", names[idx], ops[idx], next_placeholder()));
+			if ands.len() == 1 {
+				ors.push(ands.remove(0));
+			} else {
+				ors.push(format!("({})", ands.join(" AND ")));
+			}
+		}
+		Ok(format!("({})", ors.join(" OR ")))
+	}
+
+	/// Values to bind, in the `sql(..)` order.
+	pub fn collect_vals<'s>(&'s self, order_bys: &[OrderBy], vals: &mut Vec<&'s BoxedVal<'a>>) {
+		let cursor_vals = match self {
+			KeysetCursor::Vals(cursor_vals) => cursor_vals,
+			KeysetCursor::Invalid => return,
+		};
+
+		let first_desc = order_bys.first().map(|o| o.is_desc());
+		if order_bys.iter().all(|o| Some(o.is_desc()) == first_desc) {
+			vals.extend(cursor_vals.iter());
+		} else {
+			for idx in 0..cursor_vals.len() {
+				vals.extend(cursor_vals[..=idx].iter());
+			}
+		}
+	}
+}
+
+/// The column names of the order bys, for the keyset predicate and cursor.
+/// Returns `Error::KeysetInvalidOrder` if no order by or if one is an expression.
+pub(crate) fn keyset_names<'s>(order_bys: &'s [OrderBy]) -> Result<Vec<&'s str>> {
+	if order_bys.is_empty() {
+		return Err(Error::KeysetInvalidOrder);
+	}
+	order_bys.iter().map(|o| o.name().ok_or(Error::KeysetInvalidOrder)).collect()
+}
@@ -1,28 +1,53 @@
 // #![allow(unused)] // silence unused warnings while exploring (to comment out)
 
+mod agg;
+mod cond;
+mod conflict;
 mod core;
+mod cte;
 mod delete;
+mod dialect;
+mod error;
 mod insert;
+mod join;
+mod keyset;
+mod lock;
+mod order;
+mod page;
 mod select;
 pub mod sqlx_exec;
 mod update;
 mod utils;
 mod val;
+mod window;
 
+pub use crate::agg::Agg;
+pub use crate::cond::Cond;
 pub use crate::core::Field;
 pub use crate::core::HasFields;
 pub use crate::core::SqlBuilder;
 pub use crate::core::Whereable;
+pub use crate::cte::CteQuery;
 pub use crate::delete::delete;
 pub use crate::delete::delete_all;
 pub use crate::delete::DeleteSqlBuilder;
+pub use crate::dialect::{Dialect, SqlxDatabase};
+pub use crate::error::{Error, Result};
 pub use crate::insert::insert;
 pub use crate::insert::InsertSqlBuilder;
+pub use crate::join::join;
+pub use crate::join::Join;
+pub use crate::keyset::KeysetPage;
+pub use crate::order::{order, order_expr, order_window, OrderBy};
+pub use crate::page::Page;
 pub use crate::select::select;
 pub use crate::select::SelectSqlBuilder;
 pub use crate::update::update;
 pub use crate::update::update_all;
 pub use crate::update::UpdateSqlBuilder;
 pub use crate::val::Raw;
+pub use crate::val::SqlDefault;
 pub use crate::val::SqlxBindable;
+pub use crate::val::{expr, Expr};
+pub use crate::window::{window, Window, WindowFn};
 pub use sqlb_macros::Fields;
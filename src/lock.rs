@@ -0,0 +1,83 @@
+//! Row locking clause for the `SelectSqlBuilder` (e.g., `FOR UPDATE SKIP LOCKED`).
+
+use crate::utils::{check_ident, x_table_name};
+use crate::{Dialect, Error, Result};
+
+#[derive(Default)]
+pub(crate) struct RowLock {
+	/// None when only `.lock_of(..)`, `.skip_locked()` or `.nowait()` were called (invalid state).
+	pub strength: Option<LockStrength>,
+	/// `OF "table1", "table2"`
+	pub of: Vec<String>,
+	pub wait: Option<LockWait>,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum LockStrength {
+	Update,
+	NoKeyUpdate,
+	Share,
+	KeyShare,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum LockWait {
+	SkipLocked,
+	Nowait,
+}
+
+impl LockStrength {
+	fn sql(&self) -> &'static str {
+		match self {
+			LockStrength::Update => "FOR UPDATE",
+			LockStrength::NoKeyUpdate => "FOR NO KEY UPDATE",
+			LockStrength::Share => "FOR SHARE",
+			LockStrength::KeyShare => "FOR KEY SHARE",
+		}
+	}
+}
+
+impl RowLock {
+	// SQL: FOR UPDATE OF "todo" SKIP LOCKED
+	pub fn sql(&self, dialect: Dialect) -> Result<String> {
+		let strength = self.strength.ok_or(Error::LockWithoutStrength)?;
+
+		// Note: SQLite has no row locking, and MySQL only has `FOR UPDATE` and `FOR SHARE`.
+		let supported = match (dialect, strength) {
+			(Dialect::Postgres, _) => true,
+			(Dialect::MySql, LockStrength::Update | LockStrength::Share) => true,
+			(Dialect::MySql, _) | (Dialect::Sqlite, _) => false,
+		};
+		if !supported {
+			return Err(Error::DialectUnsupported {
+				dialect,
+				feature: strength.sql(),
+			});
+		}
+
+		let mut sql = format!("{} ", strength.sql());
+
+		// SQL: OF "table1", "table2"
+		if !self.of.is_empty() {
+			for table in self.of.iter() {
+				check_ident(table)?;
+			}
+			let tables = self
+				.of
+				.iter()
+				.map(|t| x_table_name(dialect, t))
+				.collect::<Vec<String>>()
+				.join(", ");
+			sql.push_str(&format!("OF {} ", tables));
+		}
+
+		// SQL: SKIP LOCKED
+		match self.wait {
+			Some(LockWait::SkipLocked) => sql.push_str("SKIP LOCKED "),
+			Some(LockWait::Nowait) => sql.push_str("NOWAIT "),
+			None => (),
+		}
+
+		Ok(sql)
+	}
+}
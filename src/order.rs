@@ -0,0 +1,171 @@
+//! `ORDER BY` items for the `SelectSqlBuilder` (column or expression, direction, `NULLS FIRST/LAST`, `COLLATE`).
+
+use crate::core::sql_value;
+use crate::utils::{check_ident, x_column_name};
+use crate::val::BoxedVal;
+use crate::window::WindowFn;
+use crate::{Dialect, Error, Expr, Result};
+
+/// Create an order by item for a column (`!` prefix for `DESC`), for `SelectSqlBuilder::add_order_by(..)`.
+///
+/// e.g., `sqlb::order("title").nulls_last().collate("C")`
+pub fn order<'a>(name: &str) -> OrderBy<'a> {
+	OrderBy::from(name)
+}
+
+/// Create an order by item for a SQL expression (see `Expr`).
+///
+/// e.g., `sqlb::order_expr(sqlb::expr("abs(\"price\" - {})").bind(10.5))` for `ORDER BY abs("price" - $1)`
+pub fn order_expr(expr: Expr<'_>) -> OrderBy<'_> {
+	OrderBy {
+		target: OrderTarget::Expr(expr),
+		dir: OrderDir::Asc,
+		nulls: None,
+		collate: None,
+	}
+}
+
+/// Create an order by item for a window function (e.g., `sqlb::order_window(WindowFn::rank().over(..)).desc()`).
+pub fn order_window<'a>(window_fn: WindowFn<'a>) -> OrderBy<'a> {
+	OrderBy::from(window_fn)
+}
+
+pub struct OrderBy<'a> {
+	target: OrderTarget<'a>,
+	dir: OrderDir,
+	nulls: Option<OrderNulls>,
+	collate: Option<String>,
+}
+
+enum OrderTarget<'a> {
+	Name(String),
+	Expr(Expr<'a>),
+	Window(WindowFn<'a>),
+}
+
+#[derive(Clone, Copy)]
+enum OrderDir {
+	Asc,
+	Desc,
+}
+
+#[derive(Clone, Copy)]
+enum OrderNulls {
+	First,
+	Last,
+}
+
+impl<'a> From<&str> for OrderBy<'a> {
+	fn from(v: &str) -> Self {
+		let (dir, name) = match v.strip_prefix('!') {
+			Some(name) => (OrderDir::Desc, name),
+			None => (OrderDir::Asc, v),
+		};
+		OrderBy {
+			target: OrderTarget::Name(name.to_string()),
+			dir,
+			nulls: None,
+			collate: None,
+		}
+	}
+}
+
+impl<'a> From<WindowFn<'a>> for OrderBy<'a> {
+	fn from(window_fn: WindowFn<'a>) -> Self {
+		OrderBy {
+			target: OrderTarget::Window(window_fn),
+			dir: OrderDir::Asc,
+			nulls: None,
+			collate: None,
+		}
+	}
+}
+
+impl<'a> OrderBy<'a> {
+	pub fn asc(mut self) -> Self {
+		self.dir = OrderDir::Asc;
+		self
+	}
+
+	pub fn desc(mut self) -> Self {
+		self.dir = OrderDir::Desc;
+		self
+	}
+
+	/// `NULLS FIRST` (not supported by MySQL).
+	pub fn nulls_first(mut self) -> Self {
+		self.nulls = Some(OrderNulls::First);
+		self
+	}
+
+	/// `NULLS LAST` (not supported by MySQL).
+	pub fn nulls_last(mut self) -> Self {
+		self.nulls = Some(OrderNulls::Last);
+		self
+	}
+
+	/// `COLLATE "name"` (e.g., `"C"`).
+	pub fn collate(mut self, name: &str) -> Self {
+		self.collate = Some(name.to_string());
+		self
+	}
+
+	pub(crate) fn is_desc(&self) -> bool {
+		matches!(self.dir, OrderDir::Desc)
+	}
+
+	/// The column name (None for an expression).
+	pub(crate) fn name(&self) -> Option<&str> {
+		match &self.target {
+			OrderTarget::Name(name) => Some(name),
+			OrderTarget::Expr(_) | OrderTarget::Window(_) => None,
+		}
+	}
+
+	// SQL: "name" COLLATE "C" DESC NULLS LAST (or abs("price" - $3) ..., or rank() OVER (...) ...)
+	pub(crate) fn sql(&self, dialect: Dialect, binding_idx: &mut usize) -> Result<String> {
+		let mut sql = match &self.target {
+			OrderTarget::Name(name) => {
+				check_ident(name)?;
+				x_column_name(dialect, name)
+			}
+			OrderTarget::Expr(expr) => sql_value(dialect, expr, binding_idx)?,
+			OrderTarget::Window(window_fn) => window_fn.sql(dialect, binding_idx)?,
+		};
+
+		// SQL: COLLATE "C"
+		if let Some(collate) = &self.collate {
+			check_ident(collate)?;
+			sql.push_str(&format!(" COLLATE {}", dialect.quote_ident(collate)));
+		}
+
+		if let OrderDir::Desc = self.dir {
+			sql.push_str(" DESC");
+		}
+
+		// SQL: NULLS LAST
+		if let Some(nulls) = self.nulls {
+			if dialect == Dialect::MySql {
+				return Err(Error::DialectUnsupported {
+					dialect,
+					feature: "NULLS FIRST/LAST",
+				});
+			}
+			match nulls {
+				OrderNulls::First => sql.push_str(" NULLS FIRST"),
+				OrderNulls::Last => sql.push_str(" NULLS LAST"),
+			}
+		}
+
+		Ok(sql)
+	}
+
+	/// Values to bind, in the `sql(..)` order.
+	pub(crate) fn collect_vals<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>) {
+		match &self.target {
+			OrderTarget::Name(_) => (),
+			OrderTarget::Expr(expr) => expr.collect_vals(vals),
+			OrderTarget::Window(window_fn) => window_fn.collect_vals(vals),
+		}
+	}
+}
@@ -0,0 +1,21 @@
+//! Offset pagination result of the `SelectSqlBuilder` `fetch_page(..)` / `fetch_page_over(..)`.
+
+/// A page of rows with the total number of rows of the select (without the paging).
+#[derive(Debug)]
+pub struct Page<D> {
+	pub items: Vec<D>,
+	pub total: i64,
+	/// The page number (1 based).
+	pub page: i64,
+	pub page_size: i64,
+}
+
+impl<D> Page<D> {
+	/// Number of pages for the total (at least 1).
+	pub fn page_count(&self) -> i64 {
+		if self.page_size <= 0 {
+			return 1;
+		}
+		((self.total + self.page_size - 1) / self.page_size).max(1)
+	}
+}
@@ -1,29 +1,133 @@
-use crate::core::{add_to_where, sql_where_items, Whereable};
-use crate::core::{OrderItem, WhereItem};
+use crate::agg::Agg;
+use crate::core::{add_to_where, check_idents, sql_where_items, where_vals, Whereable};
+use crate::cte::{CteQuery, Ctes};
+use crate::join::{sql_joins, Join, JoinItem, JoinKind};
+use crate::keyset::{keyset_names, KeysetCursor};
+use crate::lock::{LockStrength, LockWait, RowLock};
+use crate::order::OrderBy;
 use crate::sqlx_exec;
-use crate::utils::{x_column_name, x_table_name};
-use crate::{SqlBuilder, SqlxBindable};
+use crate::utils::{check_ident, x_column_name, x_table_name};
+use crate::val::BoxedVal;
+use crate::window::{Window, WindowFn};
+use crate::{Cond, Dialect, Error, KeysetPage, Page, Result, SqlBuilder, SqlxBindable, SqlxDatabase};
 use async_trait::async_trait;
-use sqlx::{Executor, FromRow, Postgres};
+use futures_core::stream::BoxStream;
+use sqlx::{Acquire, Executor, FromRow};
 
 pub fn select<'a>() -> SelectSqlBuilder<'a> {
 	SelectSqlBuilder {
+		ctes: Ctes::default(),
 		table: None,
+		table_sub: None,
+		table_alias: None,
+		joins: Vec::new(),
+		distinct: None,
 		columns: None,
 		and_wheres: Vec::new(),
-		order_bys: None,
+		keyset_cursor: None,
+		group_bys: None,
+		havings: Vec::new(),
+		windows: Vec::new(),
+		order_bys: Vec::new(),
 		limit: None,
 		offset: None,
+		lock: None,
+		compounds: Vec::new(),
 	}
 }
 
 pub struct SelectSqlBuilder<'a> {
+	ctes: Ctes<'a>,
 	table: Option<String>,
-	columns: Option<Vec<String>>,
-	and_wheres: Vec<WhereItem<'a>>,
-	order_bys: Option<Vec<OrderItem>>,
+	/// Derived table, `FROM (SELECT ...) AS "alias"` (see `table_select`).
+	table_sub: Option<Box<SelectSqlBuilder<'a>>>,
+	table_alias: Option<String>,
+	joins: Vec<JoinItem<'a>>,
+	distinct: Option<Distinct>,
+	columns: Option<Vec<SelectColumn<'a>>>,
+	and_wheres: Vec<Cond<'a>>,
+	/// Keyset pagination predicate, rendered with the where conditions (see `after_cursor`).
+	keyset_cursor: Option<KeysetCursor<'a>>,
+	group_bys: Option<Vec<String>>,
+	havings: Vec<Cond<'a>>,
+	windows: Vec<(String, Window<'a>)>,
+	order_bys: Vec<OrderBy<'a>>,
 	limit: Option<i64>,
 	offset: Option<i64>,
+	/// `FOR UPDATE ...` (see `for_update`).
+	lock: Option<RowLock>,
+	/// `UNION ALL SELECT ...` branches (see `union_all`), the order by, limit, and offset apply to the whole compound.
+	compounds: Vec<(SetOp, SelectSqlBuilder<'a>)>,
+}
+
+enum Distinct {
+	/// `SELECT DISTINCT`
+	Rows,
+	/// `SELECT DISTINCT ON ("col1", ...)` (PostgreSQL only)
+	On(Vec<String>),
+}
+
+/// Column name of the `sql_count(..)` query.
+pub(crate) const COUNT_NAME: &str = "count";
+/// Column name of the `sql_exists(..)` query.
+pub(crate) const EXISTS_NAME: &str = "exists";
+/// Column name of the `count(*) OVER()` total of the `Paging::Page { total_over: true, .. }` rendering.
+pub(crate) const PAGE_TOTAL_NAME: &str = "sqlb_total";
+
+/// The paging part of the select rendering (order by, limit, offset, and lock).
+#[derive(Clone, Copy)]
+pub(crate) enum Paging {
+	/// As set on the builder.
+	Builder,
+	/// The builder order by and lock, with this limit and offset, and optionally the `count(*) OVER()` total column.
+	Page { limit: i64, offset: i64, total_over: bool },
+	/// Without any (e.g., for the total count of the rows).
+	None,
+}
+
+#[derive(Clone, Copy)]
+enum SetOp {
+	Union,
+	UnionAll,
+	Intersect,
+	IntersectAll,
+	Except,
+	ExceptAll,
+}
+
+impl SetOp {
+	fn sql(&self) -> &'static str {
+		match self {
+			SetOp::Union => "UNION",
+			SetOp::UnionAll => "UNION ALL",
+			SetOp::Intersect => "INTERSECT",
+			SetOp::IntersectAll => "INTERSECT ALL",
+			SetOp::Except => "EXCEPT",
+			SetOp::ExceptAll => "EXCEPT ALL",
+		}
+	}
+}
+
+/// Item of the select column list.
+enum SelectColumn<'a> {
+	/// Column name (or passthrough expression with `(`).
+	Name(String),
+	Agg(Agg),
+	Window(WindowFn<'a>),
+}
+
+impl<'a> SelectColumn<'a> {
+	// SQL: "name" or count(*) AS "count" or row_number() OVER (...) AS "rn"
+	fn sql(&self, dialect: Dialect, binding_idx: &mut usize) -> Result<String> {
+		match self {
+			SelectColumn::Name(name) => {
+				check_ident(name)?;
+				Ok(x_column_name(dialect, name))
+			}
+			SelectColumn::Agg(agg) => agg.sql(dialect),
+			SelectColumn::Window(window_fn) => window_fn.sql_column(dialect, binding_idx),
+		}
+	}
 }
 
 impl<'a> SelectSqlBuilder<'a> {
@@ -32,28 +136,207 @@ impl<'a> SelectSqlBuilder<'a> {
 		self
 	}
 
+	/// Add a `name = ANY($n)` condition, with the values bound as one array (e.g., `Vec<i64>` or `&[i64]`).
+	pub fn and_where_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		self.and_where_cond(Cond::is_in(name, vals))
+	}
+
+	/// Add a `name <> ALL($n)` condition (i.e., `NOT IN`), with the values bound as one array.
+	pub fn and_where_not_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		self.and_where_cond(Cond::not_in(name, vals))
+	}
+
+	/// Add a `name IS NULL` condition.
+	pub fn and_where_null(self, name: &str) -> Self {
+		self.and_where_cond(Cond::is_null(name))
+	}
+
+	/// Add a `name IS NOT NULL` condition.
+	pub fn and_where_not_null(self, name: &str) -> Self {
+		self.and_where_cond(Cond::is_not_null(name))
+	}
+
+	/// Add a `name = $n` condition, rendered as `name IS NULL` when the value is null (e.g., `None`).
+	/// Note: `and_where_eq` always renders `= $n`, which never matches with a NULL.
+	pub fn and_where_eq_nullable<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		self.and_where_cond(Cond::eq_nullable(name, val))
+	}
+
+	/// Add a `name IS DISTINCT FROM $n` condition (null-safe `<>`).
+	pub fn and_where_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		self.and_where_cond(Cond::distinct_from(name, val))
+	}
+
+	/// Add a `name IS NOT DISTINCT FROM $n` condition (null-safe `=`).
+	pub fn and_where_not_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		self.and_where_cond(Cond::not_distinct_from(name, val))
+	}
+
+	/// Add a `name op (SELECT ...)` condition (e.g., `.and_where_select("project_id", "IN", sub_select)`).
+	pub fn and_where_select(self, name: &str, op: &'static str, select: SelectSqlBuilder<'a>) -> Self {
+		self.and_where_cond(Cond::op_select(name, op, select))
+	}
+
+	/// Add a `EXISTS (SELECT ...)` condition.
+	pub fn and_where_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.and_where_cond(Cond::exists(select))
+	}
+
+	/// Add a `NOT EXISTS (SELECT ...)` condition.
+	pub fn and_where_not_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.and_where_cond(Cond::not_exists(select))
+	}
+
+	/// Add a condition tree (see `Cond`) to the where clause (joined with `AND` with the other conditions).
+	pub fn and_where_cond(mut self, cond: Cond<'a>) -> Self {
+		self.and_wheres.push(cond);
+		self
+	}
+
 	pub fn and_where<T: 'a + SqlxBindable + Send + Sync>(mut self, name: &str, op: &'static str, val: T) -> Self {
 		add_to_where(&mut self.and_wheres, name, op, val);
 		self
 	}
 
+	/// Add a common table expression, `WITH "name" AS (...)`, with any builder as the query.
+	pub fn with(mut self, name: &str, query: impl Into<CteQuery<'a>>) -> Self {
+		self.ctes.push(name, query.into(), false);
+		self
+	}
+
+	/// Same as `with(..)`, but renders the `WITH` clause as `WITH RECURSIVE`.
+	pub fn with_recursive(mut self, name: &str, query: impl Into<CteQuery<'a>>) -> Self {
+		self.ctes.push(name, query.into(), true);
+		self
+	}
+
 	pub fn table(mut self, table: &str) -> Self {
 		self.table = Some(table.to_string());
+		self.table_sub = None;
+		self.table_alias = None;
+		self
+	}
+
+	/// Set the table with an alias (e.g., `.table_as("todo", "t")` for `FROM "todo" AS "t"`).
+	pub fn table_as(mut self, table: &str, alias: &str) -> Self {
+		self.table = Some(table.to_string());
+		self.table_sub = None;
+		self.table_alias = Some(alias.to_string());
+		self
+	}
+
+	/// Set a derived table, `FROM (SELECT ...) AS "alias"` (the alias is required by the databases).
+	pub fn table_select(mut self, select: SelectSqlBuilder<'a>, alias: &str) -> Self {
+		self.table = None;
+		self.table_sub = Some(Box::new(select));
+		self.table_alias = Some(alias.to_string());
+		self
+	}
+
+	pub fn inner_join(self, join: Join<'a>) -> Self {
+		self.add_join(JoinKind::Inner, join)
+	}
+
+	pub fn left_join(self, join: Join<'a>) -> Self {
+		self.add_join(JoinKind::Left, join)
+	}
+
+	pub fn right_join(self, join: Join<'a>) -> Self {
+		self.add_join(JoinKind::Right, join)
+	}
+
+	pub fn full_join(self, join: Join<'a>) -> Self {
+		self.add_join(JoinKind::Full, join)
+	}
+
+	/// Note: A cross join cannot have `ON` conditions.
+	pub fn cross_join(self, join: Join<'a>) -> Self {
+		self.add_join(JoinKind::Cross, join)
+	}
+
+	fn add_join(mut self, kind: JoinKind, join: Join<'a>) -> Self {
+		self.joins.push(JoinItem { kind, join });
+		self
+	}
+
+	/// Select only distinct rows, `SELECT DISTINCT ...`.
+	pub fn distinct(mut self) -> Self {
+		self.distinct = Some(Distinct::Rows);
+		self
+	}
+
+	/// Keep only the first row of each group of these columns, `SELECT DISTINCT ON ("col1", ...) ...` (PostgreSQL only).
+	/// Note: The `order_bys` must start with these columns (e.g., `.order_bys(&["user_id", "!created_at"])`).
+	pub fn distinct_on(mut self, names: &[&str]) -> Self {
+		self.distinct = Some(Distinct::On(names.iter().map(|s| s.to_string()).collect()));
 		self
 	}
 
 	pub fn columns(mut self, names: &[&str]) -> Self {
-		self.columns = Some(names.iter().map(|s| s.to_string()).collect());
+		self.columns = Some(names.iter().map(|s| SelectColumn::Name(s.to_string())).collect());
+		self
+	}
+
+	/// Add an aggregate column expression (e.g., `Agg::count_all().alias("count")`) after the current columns.
+	pub fn agg(mut self, agg: Agg) -> Self {
+		self.columns.get_or_insert_with(Vec::new).push(SelectColumn::Agg(agg));
+		self
+	}
+
+	/// Add a window function column (e.g., `WindowFn::row_number().over(..).alias("rn")`) after the current columns.
+	pub fn window_fn(mut self, window_fn: WindowFn<'a>) -> Self {
+		self.columns.get_or_insert_with(Vec::new).push(SelectColumn::Window(window_fn));
+		self
+	}
+
+	/// Define a named window, `WINDOW "name" AS (...)`, for the `WindowFn::over_named(name)` columns and order bys.
+	pub fn window(mut self, name: &str, window: Window<'a>) -> Self {
+		self.windows.push((name.to_string(), window));
+		self
+	}
+
+	pub fn group_by(mut self, names: &[&str]) -> Self {
+		self.group_bys = Some(names.iter().map(|s| s.to_string()).collect());
+		self
+	}
+
+	/// Add a `HAVING` condition (joined with `AND` with the other having conditions).
+	/// Note: The name can be an aggregate expression, e.g., `.having("count(*)", ">", 2)`.
+	pub fn having<T: 'a + SqlxBindable + Send + Sync>(mut self, name: &str, op: &'static str, val: T) -> Self {
+		add_to_where(&mut self.havings, name, op, val);
+		self
+	}
+
+	/// Add a `HAVING` condition tree (see `Cond`).
+	pub fn having_cond(mut self, cond: Cond<'a>) -> Self {
+		self.havings.push(cond);
 		self
 	}
 
 	pub fn order_bys(mut self, odrs: &[&str]) -> Self {
-		self.order_bys = Some(odrs.iter().copied().map(|o| o.into()).collect());
+		self.order_bys = odrs.iter().copied().map(|o| o.into()).collect();
 		self
 	}
 
 	pub fn order_by(mut self, odr: &str) -> Self {
-		self.order_bys = Some(vec![odr.into()]);
+		self.order_bys = vec![odr.into()];
+		self
+	}
+
+	/// Append an order by item (e.g., a tiebreaker after the `order_bys`), from a column name (`!` prefix for `DESC`)
+	/// or a `sqlb::order(..)` / `sqlb::order_expr(..)` item.
+	pub fn add_order_by(mut self, odr: impl Into<OrderBy<'a>>) -> Self {
+		self.order_bys.push(odr.into());
+		self
+	}
+
+	/// Only select the rows after the cursor in the `order_bys` order (keyset pagination),
+	/// with the cursor from the `KeysetPage` of a previous `fetch_keyset(..)`.
+	///
+	/// Note: The `order_bys` must be NOT NULL columns, and should be unique together (e.g., end with the `"id"`).
+	/// An invalid cursor returns `Error::InvalidCursor` when building the sql.
+	pub fn after_cursor(mut self, cursor: &str) -> Self {
+		self.keyset_cursor = Some(KeysetCursor::new(cursor));
 		self
 	}
 
@@ -67,36 +350,205 @@ impl<'a> SelectSqlBuilder<'a> {
 		self
 	}
 
-	pub async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64, sqlx::Error>
+	/// Add a `UNION SELECT ...` branch (duplicate rows removed).
+	///
+	/// Note: With compound branches, the `order_by(..)`, `limit(..)`, and `offset(..)` of this builder apply to the whole
+	/// compound (a branch with its own order by, limit, or offset is rendered in parentheses, not supported by SQLite).
+	pub fn union(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.compound(SetOp::Union, select)
+	}
+
+	/// Add a `UNION ALL SELECT ...` branch (see `union(..)`).
+	pub fn union_all(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.compound(SetOp::UnionAll, select)
+	}
+
+	/// Add a `INTERSECT SELECT ...` branch (see `union(..)`).
+	pub fn intersect(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.compound(SetOp::Intersect, select)
+	}
+
+	/// Add a `INTERSECT ALL SELECT ...` branch (see `union(..)`), not supported by SQLite.
+	pub fn intersect_all(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.compound(SetOp::IntersectAll, select)
+	}
+
+	/// Add a `EXCEPT SELECT ...` branch (see `union(..)`).
+	pub fn except(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.compound(SetOp::Except, select)
+	}
+
+	/// Add a `EXCEPT ALL SELECT ...` branch (see `union(..)`), not supported by SQLite.
+	pub fn except_all(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.compound(SetOp::ExceptAll, select)
+	}
+
+	fn compound(mut self, op: SetOp, select: SelectSqlBuilder<'a>) -> Self {
+		self.compounds.push((op, select));
+		self
+	}
+
+	/// Lock the selected rows with `FOR UPDATE` (rendered after `LIMIT` / `OFFSET`).
+	pub fn for_update(self) -> Self {
+		self.lock_strength(LockStrength::Update)
+	}
+
+	/// Lock the selected rows with `FOR NO KEY UPDATE` (PostgreSQL only).
+	pub fn for_no_key_update(self) -> Self {
+		self.lock_strength(LockStrength::NoKeyUpdate)
+	}
+
+	/// Lock the selected rows with `FOR SHARE`.
+	pub fn for_share(self) -> Self {
+		self.lock_strength(LockStrength::Share)
+	}
+
+	/// Lock the selected rows with `FOR KEY SHARE` (PostgreSQL only).
+	pub fn for_key_share(self) -> Self {
+		self.lock_strength(LockStrength::KeyShare)
+	}
+
+	/// Only lock the rows of these tables (or aliases), `FOR UPDATE OF "t1", "t2"`.
+	pub fn lock_of(mut self, tables: &[&str]) -> Self {
+		self.lock.get_or_insert_with(RowLock::default).of = tables.iter().map(|t| t.to_string()).collect();
+		self
+	}
+
+	/// Skip the rows already locked, `FOR UPDATE SKIP LOCKED` (e.g., job queue workers).
+	pub fn skip_locked(mut self) -> Self {
+		self.lock.get_or_insert_with(RowLock::default).wait = Some(LockWait::SkipLocked);
+		self
+	}
+
+	/// Fail rather than wait if a row is already locked, `FOR UPDATE NOWAIT`.
+	pub fn nowait(mut self) -> Self {
+		self.lock.get_or_insert_with(RowLock::default).wait = Some(LockWait::Nowait);
+		self
+	}
+
+	fn lock_strength(mut self, strength: LockStrength) -> Self {
+		self.lock.get_or_insert_with(RowLock::default).strength = Some(strength);
+		self
+	}
+
+	pub async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64>
 	where
-		DB: Executor<'q, Database = Postgres>,
+		DB: Executor<'q>,
+		DB::Database: SqlxDatabase,
 	{
 		sqlx_exec::exec(db_pool, self).await
 	}
 
-	pub async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D, sqlx::Error>
+	pub async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		sqlx_exec::fetch_as_one::<DB, D, _>(db_pool, self).await
 	}
 
-	pub async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>, sqlx::Error>
+	pub async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		sqlx_exec::fetch_as_optional::<DB, D, _>(db_pool, self).await
 	}
 
-	pub async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>, sqlx::Error>
+	pub async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		sqlx_exec::fetch_as_all::<DB, D, _>(db_pool, self).await
 	}
+
+	/// Fetch the first column of the one row as T (e.g., `.columns(&["title"])` as a `String`).
+	pub async fn fetch_scalar<'e, DB, T>(&'a self, db_pool: DB) -> Result<T>
+	where
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		(T,): for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
+	{
+		sqlx_exec::fetch_scalar::<DB, T, _>(db_pool, self).await
+	}
+
+	/// Same as `fetch_scalar(..)`, but None if no row.
+	pub async fn fetch_optional_scalar<'e, DB, T>(&'a self, db_pool: DB) -> Result<Option<T>>
+	where
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		(T,): for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
+	{
+		sqlx_exec::fetch_optional_scalar::<DB, T, _>(db_pool, self).await
+	}
+
+	/// Fetch the number of rows of the select, `SELECT count(*) FROM (SELECT ...)`.
+	pub async fn fetch_count<'e, DB>(&'a self, db_pool: DB) -> Result<i64>
+	where
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+	{
+		sqlx_exec::fetch_count::<DB>(db_pool, self).await
+	}
+
+	/// Fetch if the select has any row, `SELECT EXISTS (SELECT ...)`.
+	pub async fn fetch_exists<'e, DB>(&'a self, db_pool: DB) -> Result<bool>
+	where
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+	{
+		sqlx_exec::fetch_exists::<DB>(db_pool, self).await
+	}
+
+	/// Fetch the page (1 based) of rows and the total number of rows (with a derived count query),
+	/// see `sqlx_exec::fetch_page(..)`. The builder `limit` and `offset` are ignored.
+	pub async fn fetch_page<'c, A, D>(&'a self, db: A, page: i64, page_size: i64) -> Result<Page<D>>
+	where
+		A: Acquire<'c>,
+		A::Database: SqlxDatabase,
+		for<'t> &'t mut <A::Database as sqlx::Database>::Connection: Executor<'t, Database = A::Database>,
+		D: for<'r> FromRow<'r, <A::Database as sqlx::Database>::Row> + Unpin + Send,
+	{
+		sqlx_exec::fetch_page::<A, D>(db, self, page, page_size).await
+	}
+
+	/// Same as `fetch_page(..)`, but with the total from a `count(*) OVER()` column (one query),
+	/// see `sqlx_exec::fetch_page_over(..)`.
+	pub async fn fetch_page_over<'c, A, D>(&'a self, db: A, page: i64, page_size: i64) -> Result<Page<D>>
+	where
+		A: Acquire<'c>,
+		A::Database: SqlxDatabase,
+		for<'t> &'t mut <A::Database as sqlx::Database>::Connection: Executor<'t, Database = A::Database>,
+		D: for<'r> FromRow<'r, <A::Database as sqlx::Database>::Row> + Unpin + Send,
+	{
+		sqlx_exec::fetch_page_over::<A, D>(db, self, page, page_size).await
+	}
+
+	/// Fetch a page of rows (up to the `limit`) with the cursor for the next page (see `after_cursor`).
+	/// The cursor is read from the `order_bys` columns of the last row, so they must be in the selected columns.
+	pub async fn fetch_keyset<'e, DB, D>(&'a self, db_pool: DB) -> Result<KeysetPage<D>>
+	where
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
+	{
+		sqlx_exec::fetch_keyset::<DB, D>(db_pool, self).await
+	}
+
+	/// Fetch the rows as a `Stream` of D (rows are fetched as the stream is consumed).
+	pub fn fetch_stream<'e, DB, D>(&'a self, db_pool: DB) -> BoxStream<'e, Result<D>>
+	where
+		'a: 'e,
+		DB: Executor<'e> + 'e,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send + 'e,
+	{
+		sqlx_exec::fetch_stream::<DB, D, _>(db_pool, self)
+	}
 }
 
 impl<'a> Whereable<'a> for SelectSqlBuilder<'a> {
@@ -107,94 +559,438 @@ impl<'a> Whereable<'a> for SelectSqlBuilder<'a> {
 	fn and_where<V: 'a + SqlxBindable + Send + Sync>(self, name: &str, op: &'static str, val: V) -> Self {
 		SelectSqlBuilder::and_where(self, name, op, val)
 	}
+
+	fn and_where_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		SelectSqlBuilder::and_where_in(self, name, vals)
+	}
+
+	fn and_where_not_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		SelectSqlBuilder::and_where_not_in(self, name, vals)
+	}
+
+	fn and_where_null(self, name: &str) -> Self {
+		SelectSqlBuilder::and_where_null(self, name)
+	}
+
+	fn and_where_not_null(self, name: &str) -> Self {
+		SelectSqlBuilder::and_where_not_null(self, name)
+	}
+
+	fn and_where_eq_nullable<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		SelectSqlBuilder::and_where_eq_nullable(self, name, val)
+	}
+
+	fn and_where_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		SelectSqlBuilder::and_where_distinct_from(self, name, val)
+	}
+
+	fn and_where_not_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		SelectSqlBuilder::and_where_not_distinct_from(self, name, val)
+	}
+
+	fn and_where_select(self, name: &str, op: &'static str, select: SelectSqlBuilder<'a>) -> Self {
+		SelectSqlBuilder::and_where_select(self, name, op, select)
+	}
+
+	fn and_where_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		SelectSqlBuilder::and_where_exists(self, select)
+	}
+
+	fn and_where_not_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		SelectSqlBuilder::and_where_not_exists(self, select)
+	}
+
+	fn and_where_cond(self, cond: Cond<'a>) -> Self {
+		SelectSqlBuilder::and_where_cond(self, cond)
+	}
 }
 
-#[async_trait]
-impl<'a> SqlBuilder<'a> for SelectSqlBuilder<'a> {
-	fn sql(&self) -> String {
+// region:    --- Builder Utils
+impl<'a> SelectSqlBuilder<'a> {
+	/// Render the select with the placeholders starting at `idx_start` (e.g., as a subquery).
+	/// Returns the next binding index and the sql.
+	pub(crate) fn sql_at(&self, dialect: Dialect, idx_start: usize) -> Result<(usize, String)> {
+		self.sql_paged(dialect, idx_start, Paging::Builder)
+	}
+
+	/// True if a `count(*) OVER()` column gives the total of the select rows
+	/// (i.e., not with a `DISTINCT`, which would include it, or with compound branches, which would not have it).
+	pub(crate) fn supports_total_over(&self) -> bool {
+		self.distinct.is_none() && self.compounds.is_empty()
+	}
+
+	/// Same as `sql_at(..)`, with the paging part (order by, limit, offset, and lock) as specified.
+	pub(crate) fn sql_paged(&self, dialect: Dialect, idx_start: usize, paging: Paging) -> Result<(usize, String)> {
 		// SELECT name1, name2 FROM table_name WHERE w1 < r1, w2 = r2
+		let mut binding_idx = idx_start;
+
+		// -- Check names
+		check_idents(self.table.as_deref())?;
+		check_idents(self.table_alias.as_deref())?;
+		check_idents(self.group_bys.iter().flatten().map(|g| g.as_str()))?;
+
+		// SQL: WITH "name" AS (...)
+		let (next_idx, mut sql) = self.ctes.sql_at(dialect, binding_idx)?;
+		binding_idx = next_idx;
 
 		// SQL: SELECT
-		let mut sql = String::from("SELECT ");
+		sql.push_str("SELECT ");
+
+		// SQL: DISTINCT ON ("name1", ...)
+		match &self.distinct {
+			Some(Distinct::Rows) => sql.push_str("DISTINCT "),
+			Some(Distinct::On(names)) => sql.push_str(&format!("{} ", self.sql_distinct_on(dialect, names)?)),
+			None => (),
+		}
 
 		// SQL: name1, name2,
 		// For now, if no column, will do a "*"
-		match &self.columns {
-			Some(columns) => {
-				let names = columns.iter().map(|c| x_column_name(c)).collect::<Vec<String>>().join(", ");
-				sql.push_str(&format!("{} ", names));
-			}
-			None => sql.push_str(&format!("{} ", "*")),
+		let mut names = match &self.columns {
+			Some(columns) => columns
+				.iter()
+				.map(|c| c.sql(dialect, &mut binding_idx))
+				.collect::<Result<Vec<String>>>()?,
+			None => vec!["*".to_string()],
 		};
 
-		// SQL: FROM table_name
-		if let Some(table) = &self.table {
-			sql.push_str("FROM ");
-			sql.push_str(&x_table_name(table));
+		// SQL: , count(*) OVER() AS "sqlb_total"
+		if let Paging::Page { total_over: true, .. } = paging {
+			names.push(format!(
+				"count(*) OVER() AS {}",
+				x_column_name(dialect, PAGE_TOTAL_NAME)
+			));
 		}
+		sql.push_str(&format!("{} ", names.join(", ")));
+
+		// SQL: FROM table_name (or FROM (SELECT ...))
+		let sql_from = match (&self.table_sub, &self.table) {
+			(Some(table_sub), _) => {
+				let (next_idx, sql_sub) = table_sub.sql_at(dialect, binding_idx)?;
+				binding_idx = next_idx;
+				Some(format!("({})", sql_sub.trim_end()))
+			}
+			(None, Some(table)) => Some(x_table_name(dialect, table)),
+			(None, None) => None,
+		};
+		if let Some(sql_from) = sql_from {
+			sql.push_str(&format!("FROM {} ", sql_from));
+			// SQL: AS "t"
+			if let Some(alias) = &self.table_alias {
+				sql.push_str(&format!("AS {} ", x_table_name(dialect, alias)));
+			}
+		}
+
+		// SQL: INNER JOIN "project" AS "p" ON "p"."id" = "t"."project_id" ...
+		let (next_idx, sql_joins) = sql_joins(dialect, &self.joins, binding_idx)?;
+		binding_idx = next_idx;
+		sql.push_str(&sql_joins);
 
 		// SQL: WHERE w1 < $1, ...
+		let (next_idx, sql_where) = sql_where_items(dialect, &self.and_wheres, binding_idx)?;
+		binding_idx = next_idx;
+		let mut sql_wheres = Vec::new();
 		if !self.and_wheres.is_empty() {
-			let sql_where = sql_where_items(&self.and_wheres, 1);
-			sql.push_str(&format!("WHERE {} ", &sql_where));
+			sql_wheres.push(sql_where);
+		}
+		// SQL: ("a", "b") > ($3, $4)
+		if let Some(keyset_cursor) = &self.keyset_cursor {
+			sql_wheres.push(keyset_cursor.sql(dialect, &self.order_bys, &mut binding_idx)?);
+		}
+		if !sql_wheres.is_empty() {
+			sql.push_str(&format!("WHERE {} ", sql_wheres.join(" AND ")));
 		}
 
-		// SQL: ORDER BY
-		if let Some(order_bys) = &self.order_bys {
-			let sql_order_bys = order_bys
+		// SQL: GROUP BY "name1", "name2"
+		if let Some(group_bys) = &self.group_bys {
+			let names = group_bys
 				.iter()
-				.map::<String, _>(|o| o.into())
+				.map(|g| x_column_name(dialect, g))
 				.collect::<Vec<String>>()
 				.join(", ");
+			sql.push_str(&format!("GROUP BY {} ", names));
+		}
+
+		// SQL: HAVING count(*) > $3, ...
+		if !self.havings.is_empty() {
+			let (next_idx, sql_having) = sql_where_items(dialect, &self.havings, binding_idx)?;
+			binding_idx = next_idx;
+			sql.push_str(&format!("HAVING {} ", &sql_having));
+		}
+
+		// SQL: WINDOW "w" AS (PARTITION BY "project_id" ORDER BY "id")
+		if !self.windows.is_empty() {
+			let mut sql_windows = Vec::with_capacity(self.windows.len());
+			for (name, window) in self.windows.iter() {
+				check_ident(name)?;
+				let sql_window = window.sql(dialect, &mut binding_idx)?;
+				sql_windows.push(format!("{} AS ({})", dialect.quote_ident(name), sql_window));
+			}
+			sql.push_str(&format!("WINDOW {} ", sql_windows.join(", ")));
+		}
+
+		// SQL: UNION ALL SELECT ...
+		for (op, select) in self.compounds.iter() {
+			if self.lock.is_some() || select.lock.is_some() {
+				return Err(Error::LockWithCompound);
+			}
+			if dialect == Dialect::Sqlite && matches!(op, SetOp::IntersectAll | SetOp::ExceptAll) {
+				return Err(Error::DialectUnsupported {
+					dialect,
+					feature: op.sql(),
+				});
+			}
+			let (next_idx, sql_select) = select.sql_at(dialect, binding_idx)?;
+			binding_idx = next_idx;
+			if select.is_compound_member() {
+				sql.push_str(&format!("{} {}", op.sql(), sql_select));
+			} else {
+				sql.push_str(&format!("{} ({}) ", op.sql(), sql_select.trim_end()));
+			}
+		}
+
+		// Note: The count query is without the paging part (the order by values are not collected either).
+		if let Paging::None = paging {
+			return Ok((binding_idx, sql));
+		}
+
+		// SQL: ORDER BY
+		if !self.order_bys.is_empty() {
+			let sql_order_bys = self
+				.order_bys
+				.iter()
+				.map(|o| o.sql(dialect, &mut binding_idx))
+				.collect::<Result<Vec<String>>>()?
+				.join(", ");
 			sql.push_str(&format!("ORDER BY {} ", sql_order_bys))
 		}
 
-		// SQL: LIMIT
-		if let Some(limit) = &self.limit {
-			sql.push_str(&format!("LIMIT {limit} "))
+		// SQL: LIMIT 10 OFFSET 20
+		let (limit, offset) = match paging {
+			Paging::Page { limit, offset, .. } => (Some(limit), Some(offset)),
+			Paging::Builder | Paging::None => (self.limit, self.offset),
+		};
+		sql.push_str(&dialect.sql_limit_offset(limit, offset));
+
+		// SQL: FOR UPDATE OF "todo" SKIP LOCKED
+		if let Some(lock) = &self.lock {
+			sql.push_str(&lock.sql(dialect)?);
+		}
+
+		Ok((binding_idx, sql))
+	}
+
+	/// Number of columns of the select, None if unknown (i.e., `*` or a `t.*` column).
+	pub(crate) fn column_count(&self) -> Option<usize> {
+		let columns = self.columns.as_ref()?;
+		let has_star = columns
+			.iter()
+			.any(|c| matches!(c, SelectColumn::Name(name) if name == "*" || name.ends_with(".*")));
+		if has_star {
+			None
+		} else {
+			Some(columns.len())
+		}
+	}
+
+	/// True if the select ends with its `WHERE` clause (i.e., not a join `FROM` clause followed by the insert `ON CONFLICT`).
+	pub(crate) fn has_where(&self) -> bool {
+		!self.and_wheres.is_empty() && self.compounds.is_empty()
+	}
+
+	/// True if the select can be a compound branch as is (i.e., without parentheses).
+	fn is_compound_member(&self) -> bool {
+		let paged = !self.order_bys.is_empty() || self.limit.is_some() || self.offset.is_some() || self.lock.is_some();
+		!paged && self.compounds.is_empty() && self.ctes.is_empty()
+	}
+
+	// SQL: DISTINCT ON ("name1", "name2")
+	fn sql_distinct_on(&self, dialect: Dialect, names: &[String]) -> Result<String> {
+		if dialect != Dialect::Postgres {
+			return Err(Error::DialectUnsupported {
+				dialect,
+				feature: "DISTINCT ON",
+			});
+		}
+		check_idents(names.iter().map(|n| n.as_str()))?;
+
+		// -- Check that the names lead the order bys (in any order), as required by PostgreSQL
+		if !self.order_bys.is_empty() {
+			let leading_names: Vec<&str> = self.order_bys.iter().take(names.len()).filter_map(|o| o.name()).collect();
+			let is_leading = |name: &str| leading_names.iter().any(|leading| is_same_column(name, leading));
+			if let Some(name) = names.iter().find(|n| !is_leading(n)) {
+				return Err(Error::DistinctOnOrderMismatch { name: name.to_string() });
+			}
+		}
+
+		let names = names
+			.iter()
+			.map(|n| x_column_name(dialect, n))
+			.collect::<Vec<String>>()
+			.join(", ");
+		Ok(format!("DISTINCT ON ({})", names))
+	}
+
+	/// The column names for the next keyset cursor (the row column names, i.e., without the table qualifier),
+	/// or None if there is no next page for this number of rows.
+	pub(crate) fn keyset_row_names(&self, row_count: usize) -> Result<Option<Vec<&str>>> {
+		let names = keyset_names(&self.order_bys)?;
+		match self.limit {
+			Some(limit) if row_count > 0 && row_count as i64 >= limit => Ok(Some(
+				names.into_iter().map(|n| n.rsplit('.').next().unwrap_or(n)).collect(),
+			)),
+			_ => Ok(None),
 		}
+	}
+
+	// SQL: SELECT count(*) AS "count" FROM (SELECT ...) AS "sqlb_count"
+	// Returns the count query of the select rendered with the paging (values from `collect_vals_paged(.., paging)`).
+	pub(crate) fn sql_count(&self, dialect: Dialect, paging: Paging) -> Result<String> {
+		let (_, sql) = self.sql_paged(dialect, 1, paging)?;
+		Ok(format!(
+			"SELECT count(*) AS {} FROM ({}) AS {} ",
+			x_column_name(dialect, COUNT_NAME),
+			sql.trim_end(),
+			x_table_name(dialect, "sqlb_count")
+		))
+	}
+
+	// SQL: SELECT EXISTS (SELECT ...) AS "exists"
+	// Returns the exists query of the select (values from `collect_vals(..)`).
+	pub(crate) fn sql_exists(&self, dialect: Dialect) -> Result<String> {
+		let (_, sql) = self.sql_at(dialect, 1)?;
+		Ok(format!(
+			"SELECT EXISTS ({}) AS {} ",
+			sql.trim_end(),
+			x_column_name(dialect, EXISTS_NAME)
+		))
+	}
 
-		// SQL: OFFSET
-		if let Some(offset) = &self.offset {
-			sql.push_str(&format!("OFFSET {offset} "))
+	/// Collect the values to bind, in the same order as the `sql_at(..)` rendering.
+	pub(crate) fn collect_vals<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>) {
+		self.collect_vals_paged(vals, Paging::Builder);
+	}
+
+	/// Same as `collect_vals(..)`, for the `sql_paged(.., paging)` rendering.
+	pub(crate) fn collect_vals_paged<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>, paging: Paging) {
+		// Note: The CTE, column, derived table and join values come first, as they are before the where clause in the sql.
+		self.ctes.collect_vals(vals);
+		for column in self.columns.iter().flatten() {
+			if let SelectColumn::Window(window_fn) = column {
+				window_fn.collect_vals(vals);
+			}
+		}
+		if let Some(table_sub) = &self.table_sub {
+			table_sub.collect_vals(vals);
+		}
+		vals.extend(self.joins.iter().flat_map(|j| j.vals()));
+		vals.extend(where_vals(&self.and_wheres));
+		if let Some(keyset_cursor) = &self.keyset_cursor {
+			keyset_cursor.collect_vals(&self.order_bys, vals);
+		}
+		vals.extend(where_vals(&self.havings));
+		for (_, window) in self.windows.iter() {
+			window.collect_vals(vals);
+		}
+		for (_, select) in self.compounds.iter() {
+			select.collect_vals(vals);
+		}
+		if let Paging::None = paging {
+			return;
 		}
+		for order_by in self.order_bys.iter() {
+			order_by.collect_vals(vals);
+		}
+	}
+}
 
-		sql
+/// True if the column names are the same column, comparing only the unqualified names if one of them is unqualified
+/// (e.g., `"t.project_id"` and `"project_id"`).
+fn is_same_column(a: &str, b: &str) -> bool {
+	if a.contains('.') == b.contains('.') {
+		a == b
+	} else {
+		a.rsplit('.').next() == b.rsplit('.').next()
 	}
+}
+// endregion: --- Builder Utils
 
-	fn vals(&'a self) -> Box<dyn Iterator<Item = &Box<dyn SqlxBindable + 'a + Send + Sync>> + 'a + Send> {
-		let iter = self.and_wheres.iter().map(|wi| &wi.val);
-		Box::new(iter)
+#[async_trait]
+impl<'a> SqlBuilder<'a> for SelectSqlBuilder<'a> {
+	fn try_sql_for(&self, dialect: Dialect) -> Result<String> {
+		let (_, sql) = self.sql_at(dialect, 1)?;
+		Ok(sql)
+	}
+
+	fn vals(&'a self) -> Box<dyn Iterator<Item = &'a Box<dyn SqlxBindable + 'a + Send + Sync>> + 'a + Send> {
+		let mut vals = Vec::new();
+		self.collect_vals(&mut vals);
+		Box::new(vals.into_iter())
 	}
 
-	async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64, sqlx::Error>
+	async fn exec<'q, DB>(&'a self, db_pool: DB) -> Result<u64>
 	where
-		DB: Executor<'q, Database = Postgres>,
+		DB: Executor<'q>,
+		DB::Database: SqlxDatabase,
 	{
 		Self::exec(self, db_pool).await
 	}
 
-	async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D, sqlx::Error>
+	async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_one::<DB, D>(self, db_pool).await
 	}
 
-	async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>, sqlx::Error>
+	async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_optional::<DB, D>(self, db_pool).await
 	}
 
-	async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>, sqlx::Error>
+	async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_all::<DB, D>(self, db_pool).await
 	}
+
+	fn fetch_stream<'e, DB, D>(&'a self, db_pool: DB) -> BoxStream<'e, Result<D>>
+	where
+		'a: 'e,
+		Self: Sync,
+		DB: Executor<'e> + 'e,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send + 'e,
+	{
+		Self::fetch_stream::<DB, D>(self, db_pool)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Paging;
+	use crate::Dialect;
+
+	#[test]
+	fn select_sql_paged_total_over() {
+		let sb = crate::select().table("todo").columns(&["id", "title"]).order_by("id");
+		let paging = Paging::Page {
+			limit: 2,
+			offset: 4,
+			total_over: true,
+		};
+		let (_, sql) = sb.sql_paged(Dialect::Postgres, 1, paging).unwrap();
+		assert_eq!(
+			sql,
+			r#"SELECT "id", "title", count(*) OVER() AS "sqlb_total" FROM "todo" ORDER BY "id" LIMIT 2 OFFSET 4 "#
+		);
+	}
 }
@@ -1,28 +1,28 @@
-////////////////////////////////////
-// sqlx-exec - module for the sqlx query executor
-////
+//! sqlx-exec - module for the sqlx query executor
+//!
+//! The functions are generic over the sqlx database of the executor (see `SqlxDatabase`),
+//! and build the sql with its dialect.
 
-use crate::SqlBuilder;
-use sqlx::{postgres::PgArguments, Execute, Executor, FromRow, Postgres};
+use crate::select::{Paging, COUNT_NAME, EXISTS_NAME, PAGE_TOTAL_NAME};
+use crate::val::BoxedVal;
+use crate::{Error, KeysetPage, Page, Result, SelectSqlBuilder, SqlBuilder, SqlxDatabase};
+use futures_core::stream::BoxStream;
+use sqlx::query::Query;
+use sqlx::{Acquire, Executor, FromRow};
 
 /// Build a sqlx::query_as for the D (Data) generic type, binds the values, and does a .fetch_one and returns E
-pub async fn fetch_as_one<'e, 'q, DB, D, Q>(db_pool: DB, sb: &'q Q) -> Result<D, sqlx::Error>
+pub async fn fetch_as_one<'e, 'q, DB, D, Q>(db_pool: DB, sb: &'q Q) -> Result<D>
 where
-	DB: Executor<'e, Database = Postgres>,
-	D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+	DB: Executor<'e>,
+	DB::Database: SqlxDatabase,
+	D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	Q: SqlBuilder<'q>,
 {
-	let sql = sb.sql();
-	let vals = sb.vals();
+	let sql = sb.try_sql_for(<DB::Database as SqlxDatabase>::DIALECT)?;
+	let query = bind_query::<DB::Database, _>(&sql, sb)?;
 
-	// build temp query for binding
-	let mut query = sqlx::query::<sqlx::Postgres>(&sql);
-	for val in vals.into_iter() {
-		query = val.bind_query(query);
-	}
-
-	// create the QueryAs
-	let query = sqlx::query_as_with::<sqlx::Postgres, D, PgArguments>(&sql, query.take_arguments().unwrap());
+	// map the rows to D
+	let query = query.try_map(|row| D::from_row(&row));
 
 	// exec and return
 	let r = query.fetch_one(db_pool).await?;
@@ -30,23 +30,18 @@ where
 }
 
 /// Build a sqlx::query_as for the D (Data) generic type, binds the values, and does a .fetch_one and returns E
-pub async fn fetch_as_optional<'e, 'q, DB, D, Q>(db_pool: DB, sb: &'q Q) -> Result<Option<D>, sqlx::Error>
+pub async fn fetch_as_optional<'e, 'q, DB, D, Q>(db_pool: DB, sb: &'q Q) -> Result<Option<D>>
 where
-	DB: Executor<'e, Database = Postgres>,
-	D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+	DB: Executor<'e>,
+	DB::Database: SqlxDatabase,
+	D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	Q: SqlBuilder<'q>,
 {
-	let sql = sb.sql();
-	let vals = sb.vals();
+	let sql = sb.try_sql_for(<DB::Database as SqlxDatabase>::DIALECT)?;
+	let query = bind_query::<DB::Database, _>(&sql, sb)?;
 
-	// build temp query for binding
-	let mut query = sqlx::query::<sqlx::Postgres>(&sql);
-	for val in vals.into_iter() {
-		query = val.bind_query(query);
-	}
-
-	// create the QueryAs
-	let query = sqlx::query_as_with::<sqlx::Postgres, D, PgArguments>(&sql, query.take_arguments().unwrap());
+	// map the rows to D
+	let query = query.try_map(|row| D::from_row(&row));
 
 	// exec and return
 	let r = query.fetch_optional(db_pool).await?;
@@ -54,42 +49,294 @@ where
 }
 
 /// Build a sqlx::query_as for the D (Data) generic type, binds the values, and does a .fetch_all and returns Vec<E>
-pub async fn fetch_as_all<'e, 'q, DB, D, Q>(db_pool: DB, sb: &'q Q) -> Result<Vec<D>, sqlx::Error>
+pub async fn fetch_as_all<'e, 'q, DB, D, Q>(db_pool: DB, sb: &'q Q) -> Result<Vec<D>>
 where
-	DB: Executor<'e, Database = Postgres>,
-	D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+	DB: Executor<'e>,
+	DB::Database: SqlxDatabase,
+	D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	Q: SqlBuilder<'q>,
 {
-	let sql = sb.sql();
-	let vals = sb.vals();
-
-	// build temp query for binding
-	let mut query = sqlx::query::<sqlx::Postgres>(&sql);
-	for val in vals.into_iter() {
-		query = val.bind_query(query);
-	}
+	let sql = sb.try_sql_for(<DB::Database as SqlxDatabase>::DIALECT)?;
+	let query = bind_query::<DB::Database, _>(&sql, sb)?;
 
-	// create the QueryAs
-	let query = sqlx::query_as_with::<sqlx::Postgres, D, PgArguments>(&sql, query.take_arguments().unwrap());
+	// map the rows to D
+	let query = query.try_map(|row| D::from_row(&row));
 
 	// exec and return
 	let r = query.fetch_all(db_pool).await?;
 	Ok(r)
 }
 
-pub async fn exec<'e, 'q, DB, Q>(db_pool: DB, sb: &'q Q) -> Result<u64, sqlx::Error>
+/// Fetch the first column of the one row as T (e.g., `i64` for a `count(*)`, or the `id` of an insert returning).
+pub async fn fetch_scalar<'e, 'q, DB, T, Q>(db_pool: DB, sb: &'q Q) -> Result<T>
 where
-	DB: Executor<'e, Database = Postgres>,
+	DB: Executor<'e>,
+	DB::Database: SqlxDatabase,
+	(T,): for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	Q: SqlBuilder<'q>,
 {
-	let sql = sb.sql();
-	let vals = sb.vals();
-	let mut query = sqlx::query::<sqlx::Postgres>(&sql);
-	for val in vals.into_iter() {
-		query = val.bind_query(query);
-	}
+	let (val,) = fetch_as_one::<DB, (T,), Q>(db_pool, sb).await?;
+	Ok(val)
+}
+
+/// Same as `fetch_scalar(..)`, but None if no row.
+pub async fn fetch_optional_scalar<'e, 'q, DB, T, Q>(db_pool: DB, sb: &'q Q) -> Result<Option<T>>
+where
+	DB: Executor<'e>,
+	DB::Database: SqlxDatabase,
+	(T,): for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
+	Q: SqlBuilder<'q>,
+{
+	let val = fetch_as_optional::<DB, (T,), Q>(db_pool, sb).await?;
+	Ok(val.map(|(val,)| val))
+}
+
+/// Fetch the number of rows of the select, wrapped as `SELECT count(*) FROM (SELECT ...)`
+/// (so that the group by, distinct, limit, and offset of the select apply).
+pub async fn fetch_count<'e, 'q, DB>(db_pool: DB, sb: &'q SelectSqlBuilder<'q>) -> Result<i64>
+where
+	DB: Executor<'e>,
+	DB::Database: SqlxDatabase,
+{
+	let sql = sb.sql_count(<DB::Database as SqlxDatabase>::DIALECT, Paging::Builder)?;
+	let query = bind_query::<DB::Database, _>(&sql, sb)?;
+
+	let row = query.fetch_one(db_pool).await?;
+	<DB::Database as SqlxDatabase>::row_i64(&row, COUNT_NAME)
+}
+
+/// Fetch if the select has any row, wrapped as `SELECT EXISTS (SELECT ...)`.
+pub async fn fetch_exists<'e, 'q, DB>(db_pool: DB, sb: &'q SelectSqlBuilder<'q>) -> Result<bool>
+where
+	DB: Executor<'e>,
+	DB::Database: SqlxDatabase,
+{
+	let sql = sb.sql_exists(<DB::Database as SqlxDatabase>::DIALECT)?;
+	let query = bind_query::<DB::Database, _>(&sql, sb)?;
+
+	let row = query.fetch_one(db_pool).await?;
+	<DB::Database as SqlxDatabase>::row_bool(&row, EXISTS_NAME)
+}
+
+/// Build a sqlx::query for the select, binds the values, and does a .fetch_all returning a `KeysetPage` of D
+/// with the next cursor encoded from the last row (see `SelectSqlBuilder::after_cursor`).
+pub async fn fetch_keyset<'e, 'q, DB, D>(db_pool: DB, sb: &'q SelectSqlBuilder<'q>) -> Result<KeysetPage<D>>
+where
+	DB: Executor<'e>,
+	DB::Database: SqlxDatabase,
+	D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
+{
+	let sql = sb.try_sql_for(<DB::Database as SqlxDatabase>::DIALECT)?;
+	let query = bind_query::<DB::Database, _>(&sql, sb)?;
+
+	// exec, and map the rows to D
+	let rows = query.fetch_all(db_pool).await?;
+	let items = rows
+		.iter()
+		.map(|row| D::from_row(row))
+		.collect::<core::result::Result<Vec<D>, _>>()?;
+
+	// encode the next cursor from the last row
+	let next_cursor = match (sb.keyset_row_names(rows.len())?, rows.last()) {
+		(Some(names), Some(row)) => Some(<DB::Database as SqlxDatabase>::row_cursor(row, &names)?),
+		_ => None,
+	};
+
+	Ok(KeysetPage { items, next_cursor })
+}
+
+/// Fetch the page (1 based) of the select rows with `LIMIT page_size OFFSET (page - 1) * page_size`,
+/// and the total from a count query derived from the same select (without the order by, limit, and offset).
+/// Both queries are executed on the same connection (`db` can be a pool, connection, or transaction).
+/// Returns `Error::InvalidPage` for a `page` or `page_size` below 1.
+pub async fn fetch_page<'c, 'q, A, D>(db: A, sb: &'q SelectSqlBuilder<'q>, page: i64, page_size: i64) -> Result<Page<D>>
+where
+	A: Acquire<'c>,
+	A::Database: SqlxDatabase,
+	for<'t> &'t mut <A::Database as sqlx::Database>::Connection: Executor<'t, Database = A::Database>,
+	D: for<'r> FromRow<'r, <A::Database as sqlx::Database>::Row> + Unpin + Send,
+{
+	let offset = page_offset(page, page_size)?;
+	let mut conn = db.acquire().await?;
+
+	let items = fetch_page_rows::<A::Database>(&mut *conn, sb, offset, page_size, false).await?;
+	let items = items
+		.iter()
+		.map(|row| D::from_row(row))
+		.collect::<core::result::Result<Vec<D>, _>>()?;
+	let total = fetch_page_total::<A::Database>(&mut *conn, sb).await?;
+
+	Ok(Page {
+		items,
+		total,
+		page,
+		page_size,
+	})
+}
+
+/// Same as `fetch_page(..)`, but with the total from a `count(*) OVER()` column of the page query
+/// (one query, except for an empty page, or a `DISTINCT` or compound select, which use the count query).
+pub async fn fetch_page_over<'c, 'q, A, D>(
+	db: A,
+	sb: &'q SelectSqlBuilder<'q>,
+	page: i64,
+	page_size: i64,
+) -> Result<Page<D>>
+where
+	A: Acquire<'c>,
+	A::Database: SqlxDatabase,
+	for<'t> &'t mut <A::Database as sqlx::Database>::Connection: Executor<'t, Database = A::Database>,
+	D: for<'r> FromRow<'r, <A::Database as sqlx::Database>::Row> + Unpin + Send,
+{
+	let offset = page_offset(page, page_size)?;
+	let mut conn = db.acquire().await?;
+
+	let total_over = sb.supports_total_over();
+	let rows = fetch_page_rows::<A::Database>(&mut *conn, sb, offset, page_size, total_over).await?;
+	let items = rows
+		.iter()
+		.map(|row| D::from_row(row))
+		.collect::<core::result::Result<Vec<D>, _>>()?;
+	let total = match rows.first() {
+		Some(row) if total_over => <A::Database as SqlxDatabase>::row_i64(row, PAGE_TOTAL_NAME)?,
+		_ => fetch_page_total::<A::Database>(&mut *conn, sb).await?,
+	};
 
-	let r = query.execute(db_pool).await?.rows_affected();
+	Ok(Page {
+		items,
+		total,
+		page,
+		page_size,
+	})
+}
+
+/// Build a sqlx::query_as for the D (Data) generic type, binds the values, and does a .fetch returning a Stream of D
+/// (rows are fetched as the stream is consumed, rather than all loaded in memory).
+///
+/// Note: The builder (and its bound values) is borrowed for the lifetime of the stream.
+pub fn fetch_stream<'e, 'q, DB, D, Q>(db_pool: DB, sb: &'q Q) -> BoxStream<'e, Result<D>>
+where
+	'q: 'e,
+	DB: Executor<'e> + 'e,
+	DB::Database: SqlxDatabase,
+	D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send + 'e,
+	Q: SqlBuilder<'q> + Sync,
+{
+	Box::pin(async_stream::try_stream! {
+		let sql = sb.try_sql_for(<DB::Database as SqlxDatabase>::DIALECT)?;
+		let query = bind_query::<DB::Database, _>(&sql, sb)?;
+
+		// map the rows to D
+		let query = query.try_map(|row| D::from_row(&row));
+
+		// exec and yield the rows as they come
+		for await item in query.fetch(db_pool) {
+			yield item?;
+		}
+	})
+}
+
+pub async fn exec<'e, 'q, DB, Q>(db_pool: DB, sb: &'q Q) -> Result<u64>
+where
+	DB: Executor<'e>,
+	DB::Database: SqlxDatabase,
+	Q: SqlBuilder<'q>,
+{
+	let sql = sb.try_sql_for(<DB::Database as SqlxDatabase>::DIALECT)?;
+	let query = bind_query::<DB::Database, _>(&sql, sb)?;
+
+	let r = query.execute(db_pool).await?;
+	let r = <DB::Database as SqlxDatabase>::rows_affected(&r);
 
 	Ok(r)
 }
+
+// region:    --- Support
+
+/// The `(page - 1) * page_size` offset of the page (1 based).
+fn page_offset(page: i64, page_size: i64) -> Result<i64> {
+	if page < 1 || page_size < 1 {
+		return Err(Error::InvalidPage { page, page_size });
+	}
+	(page - 1).checked_mul(page_size).ok_or(Error::InvalidPage { page, page_size })
+}
+
+async fn fetch_page_rows<X>(
+	conn: &mut X::Connection,
+	sb: &SelectSqlBuilder<'_>,
+	offset: i64,
+	page_size: i64,
+	total_over: bool,
+) -> Result<Vec<X::Row>>
+where
+	X: SqlxDatabase,
+	for<'t> &'t mut X::Connection: Executor<'t, Database = X>,
+{
+	let paging = Paging::Page {
+		limit: page_size,
+		offset,
+		total_over,
+	};
+	let (_, sql) = sb.sql_paged(X::DIALECT, 1, paging)?;
+	let mut vals = Vec::new();
+	sb.collect_vals_paged(&mut vals, paging);
+	let query = bind_vals::<X>(&sql, vals.into_iter())?;
+
+	Ok(query.fetch_all(conn).await?)
+}
+
+async fn fetch_page_total<X>(conn: &mut X::Connection, sb: &SelectSqlBuilder<'_>) -> Result<i64>
+where
+	X: SqlxDatabase,
+	for<'t> &'t mut X::Connection: Executor<'t, Database = X>,
+{
+	let sql = sb.sql_count(X::DIALECT, Paging::None)?;
+	let mut vals = Vec::new();
+	sb.collect_vals_paged(&mut vals, Paging::None);
+	let query = bind_vals::<X>(&sql, vals.into_iter())?;
+
+	let row = query.fetch_one(conn).await?;
+	X::row_i64(&row, COUNT_NAME)
+}
+
+/// Build the sqlx query for the sql and bind the builder values (in order).
+fn bind_query<'s, 'q, X, Q>(sql: &'s str, sb: &'q Q) -> Result<Query<'s, X, X::Args<'s>>>
+where
+	'q: 's,
+	X: SqlxDatabase,
+	Q: SqlBuilder<'q>,
+{
+	bind_vals::<X>(sql, sb.vals())
+}
+
+/// Build the sqlx query for the sql and bind the values (in order).
+/// Returns an error if the number of bind parameters is above the max of the database (see `Dialect::max_bind_params()`),
+/// or if a value cannot be bound for the database (see `SqlxBindable::bindable_for(..)`).
+pub(crate) fn bind_vals<'s, 'q: 's, X: SqlxDatabase>(
+	sql: &'s str,
+	vals: impl Iterator<Item = &'q BoxedVal<'q>>,
+) -> Result<Query<'s, X, X::Args<'s>>> {
+	let vals: Vec<_> = vals.collect();
+
+	// Note: Raw values are not bound, and Expr values bind their inner values.
+	let count: usize = vals.iter().map(|val| val.bind_count()).sum();
+	let max = X::DIALECT.max_bind_params();
+	if count > max {
+		return Err(Error::TooManyBindParams { count, max });
+	}
+	if vals.iter().any(|val| !val.bindable_for(X::DIALECT)) {
+		return Err(Error::DialectUnsupported {
+			dialect: X::DIALECT,
+			feature: "value binding",
+		});
+	}
+
+	let mut query = X::query(sql);
+	for val in vals {
+		query = X::bind_val(val.as_ref(), query);
+	}
+
+	Ok(query)
+}
+
+// endregion: --- Support
@@ -1,15 +1,24 @@
-use crate::core::{add_to_where, into_returnings, sql_returnings, sql_where_items};
-use crate::core::{WhereItem, Whereable};
+use crate::core::Whereable;
+use crate::core::{
+	add_to_where, check_idents, into_returnings, sql_returnings, sql_tables, sql_value, sql_where_items, where_vals,
+};
+use crate::cte::{CteQuery, Ctes};
 use crate::utils::{x_column_name, x_table_name};
-use crate::{sqlx_exec, Field, SqlBuilder, SqlxBindable};
+use crate::val::BoxedVal;
+use crate::{sqlx_exec, Cond, Dialect, Error, Field, Result, SelectSqlBuilder, SqlBuilder, SqlxBindable, SqlxDatabase};
 use async_trait::async_trait;
-use sqlx::{Executor, FromRow, Postgres};
+use futures_core::stream::BoxStream;
+use sqlx::{Executor, FromRow};
 
 pub fn update<'a>() -> UpdateSqlBuilder<'a> {
 	UpdateSqlBuilder {
+		ctes: Ctes::default(),
 		guard_all: true,
 		table: None,
 		data: Vec::new(),
+		data_cols: Vec::new(),
+		froms: Vec::new(),
+		version: None,
 		returnings: None,
 		and_wheres: Vec::new(),
 	}
@@ -17,23 +26,46 @@ pub fn update<'a>() -> UpdateSqlBuilder<'a> {
 
 pub fn update_all<'a>() -> UpdateSqlBuilder<'a> {
 	UpdateSqlBuilder {
+		ctes: Ctes::default(),
 		guard_all: false,
 		table: None,
 		data: Vec::new(),
+		data_cols: Vec::new(),
+		froms: Vec::new(),
+		version: None,
 		returnings: None,
 		and_wheres: Vec::new(),
 	}
 }
 
 pub struct UpdateSqlBuilder<'a> {
+	ctes: Ctes<'a>,
 	guard_all: bool,
 	table: Option<String>,
 	data: Vec<Field<'a>>,
+	/// Column to column sets, `"name" = "s"."name"` (after the `data` fields).
+	data_cols: Vec<(String, String)>,
+	/// The `FROM` tables, with their optional alias.
+	froms: Vec<(String, Option<String>)>,
+	/// The version column and its expected value (see `with_version`).
+	version: Option<(String, Box<dyn SqlxBindable + 'a + Send + Sync>)>,
 	returnings: Option<Vec<String>>,
-	and_wheres: Vec<WhereItem<'a>>,
+	and_wheres: Vec<Cond<'a>>,
 }
 
 impl<'a> UpdateSqlBuilder<'a> {
+	/// Add a common table expression, `WITH "name" AS (...)`, with any builder as the query.
+	pub fn with(mut self, name: &str, query: impl Into<CteQuery<'a>>) -> Self {
+		self.ctes.push(name, query.into(), false);
+		self
+	}
+
+	/// Same as `with(..)`, but renders the `WITH` clause as `WITH RECURSIVE`.
+	pub fn with_recursive(mut self, name: &str, query: impl Into<CteQuery<'a>>) -> Self {
+		self.ctes.push(name, query.into(), true);
+		self
+	}
+
 	pub fn table(mut self, table: &str) -> Self {
 		self.table = Some(table.to_string());
 		self
@@ -44,6 +76,41 @@ impl<'a> UpdateSqlBuilder<'a> {
 		self
 	}
 
+	/// Set columns from other columns (nothing bound), e.g., `&[("status", "s.status")]` for `"status" = "s"."status"`.
+	/// Typically with a `.from(..)` table.
+	pub fn data_cols(mut self, sets: &[(&str, &str)]) -> Self {
+		self.data_cols = sets.iter().map(|(name, col)| (name.to_string(), col.to_string())).collect();
+		self
+	}
+
+	/// Add a table to the `UPDATE ... FROM "table"` (PostgreSQL and SQLite).
+	/// Note: Use `Cond::cols_eq(..)` to join it with the updated table (e.g., `Cond::cols_eq("todo.id", "staging.todo_id")`).
+	pub fn from(mut self, table: &str) -> Self {
+		self.froms.push((table.to_string(), None));
+		self
+	}
+
+	/// Same as `from(..)`, with an alias, `FROM "table" AS "alias"`.
+	pub fn from_as(mut self, table: &str, alias: &str) -> Self {
+		self.froms.push((table.to_string(), Some(alias.to_string())));
+		self
+	}
+
+	/// Optimistic locking on a version column, adds `"version" = "version" + 1` to the set,
+	/// and `AND "version" = $n` (the expected version) to the where clause.
+	/// `exec` and `fetch_one/optional/all` then return `Error::StaleVersion` if no row was updated.
+	/// A version only update (without data) is valid, and only increments the version.
+	/// The data cannot set the version column (`Error::VersionInData`).
+	///
+	/// Note: `fetch_stream` and the `sqlx_exec` functions (e.g., `sqlx_exec::exec(..)`) do not return `Error::StaleVersion`,
+	///       so an empty result (or a `0` count) is the stale version there.
+	///
+	/// e.g., `sqlb::update().table("todo").data(fields).and_where_eq("id", id).with_version("version", 3)`
+	pub fn with_version<T: 'a + SqlxBindable + Send + Sync>(mut self, name: &str, expected: T) -> Self {
+		self.version = Some((name.to_string(), Box::new(expected)));
+		self
+	}
+
 	pub fn and_where<T: 'a + SqlxBindable + Send + Sync>(mut self, name: &str, op: &'static str, val: T) -> Self {
 		add_to_where(&mut self.and_wheres, name, op, val);
 		self
@@ -54,40 +121,130 @@ impl<'a> UpdateSqlBuilder<'a> {
 		self
 	}
 
+	/// Add a `name = ANY($n)` condition, with the values bound as one array (e.g., `Vec<i64>` or `&[i64]`).
+	pub fn and_where_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		self.and_where_cond(Cond::is_in(name, vals))
+	}
+
+	/// Add a `name <> ALL($n)` condition (i.e., `NOT IN`), with the values bound as one array.
+	pub fn and_where_not_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		self.and_where_cond(Cond::not_in(name, vals))
+	}
+
+	/// Add a `name IS NULL` condition.
+	pub fn and_where_null(self, name: &str) -> Self {
+		self.and_where_cond(Cond::is_null(name))
+	}
+
+	/// Add a `name IS NOT NULL` condition.
+	pub fn and_where_not_null(self, name: &str) -> Self {
+		self.and_where_cond(Cond::is_not_null(name))
+	}
+
+	/// Add a `name = $n` condition, rendered as `name IS NULL` when the value is null (e.g., `None`).
+	/// Note: `and_where_eq` always renders `= $n`, which never matches with a NULL.
+	pub fn and_where_eq_nullable<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		self.and_where_cond(Cond::eq_nullable(name, val))
+	}
+
+	/// Add a `name IS DISTINCT FROM $n` condition (null-safe `<>`).
+	pub fn and_where_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		self.and_where_cond(Cond::distinct_from(name, val))
+	}
+
+	/// Add a `name IS NOT DISTINCT FROM $n` condition (null-safe `=`).
+	pub fn and_where_not_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		self.and_where_cond(Cond::not_distinct_from(name, val))
+	}
+
+	/// Add a `name op (SELECT ...)` condition (e.g., `.and_where_select("project_id", "IN", sub_select)`).
+	pub fn and_where_select(self, name: &str, op: &'static str, select: SelectSqlBuilder<'a>) -> Self {
+		self.and_where_cond(Cond::op_select(name, op, select))
+	}
+
+	/// Add a `EXISTS (SELECT ...)` condition.
+	pub fn and_where_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.and_where_cond(Cond::exists(select))
+	}
+
+	/// Add a `NOT EXISTS (SELECT ...)` condition.
+	pub fn and_where_not_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		self.and_where_cond(Cond::not_exists(select))
+	}
+
+	/// Add a condition tree (see `Cond`) to the where clause (joined with `AND` with the other conditions).
+	pub fn and_where_cond(mut self, cond: Cond<'a>) -> Self {
+		self.and_wheres.push(cond);
+		self
+	}
+
 	pub fn returning(mut self, names: &[&str]) -> Self {
 		self.returnings = into_returnings(self.returnings, names);
 		self
 	}
 
-	pub async fn exec<'q, E>(&'a self, db_pool: E) -> Result<u64, sqlx::Error>
+	/// Execute the update and return the number of affected rows
+	/// (`Error::StaleVersion` if none with a `.with_version(..)`).
+	pub async fn exec<'q, E>(&'a self, db_pool: E) -> Result<u64>
+	where
+		E: Executor<'q>,
+		E::Database: SqlxDatabase,
+	{
+		let count = sqlx_exec::exec(db_pool, self).await?;
+		if count == 0 && self.version.is_some() {
+			return Err(self.stale_version_error());
+		}
+		Ok(count)
+	}
+
+	pub async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D>
 	where
-		E: Executor<'q, Database = Postgres>,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
-		sqlx_exec::exec(db_pool, self).await
+		match sqlx_exec::fetch_as_one::<DB, D, _>(db_pool, self).await {
+			Err(Error::Sqlx(sqlx::Error::RowNotFound)) if self.version.is_some() => Err(self.stale_version_error()),
+			res => res,
+		}
 	}
 
-	pub async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D, sqlx::Error>
+	pub async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
-		sqlx_exec::fetch_as_one::<DB, D, _>(db_pool, self).await
+		let item = sqlx_exec::fetch_as_optional::<DB, D, _>(db_pool, self).await?;
+		if item.is_none() && self.version.is_some() {
+			return Err(self.stale_version_error());
+		}
+		Ok(item)
 	}
 
-	pub async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>, sqlx::Error>
+	pub async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
-		sqlx_exec::fetch_as_optional::<DB, D, _>(db_pool, self).await
+		let items = sqlx_exec::fetch_as_all::<DB, D, _>(db_pool, self).await?;
+		if items.is_empty() && self.version.is_some() {
+			return Err(self.stale_version_error());
+		}
+		Ok(items)
 	}
 
-	pub async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>, sqlx::Error>
+	/// Fetch the rows as a `Stream` of D (rows are fetched as the stream is consumed).
+	/// Note: Does not return `Error::StaleVersion` (see `with_version`).
+	pub fn fetch_stream<'e, DB, D>(&'a self, db_pool: DB) -> BoxStream<'e, Result<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		'a: 'e,
+		DB: Executor<'e> + 'e,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send + 'e,
 	{
-		sqlx_exec::fetch_as_all::<DB, D, _>(db_pool, self).await
+		sqlx_exec::fetch_stream::<DB, D, _>(db_pool, self)
 	}
 }
 
@@ -99,99 +256,228 @@ impl<'a> Whereable<'a> for UpdateSqlBuilder<'a> {
 	fn and_where<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, op: &'static str, val: T) -> Self {
 		UpdateSqlBuilder::and_where(self, name, op, val)
 	}
+
+	fn and_where_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		UpdateSqlBuilder::and_where_in(self, name, vals)
+	}
+
+	fn and_where_not_in<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, vals: T) -> Self {
+		UpdateSqlBuilder::and_where_not_in(self, name, vals)
+	}
+
+	fn and_where_null(self, name: &str) -> Self {
+		UpdateSqlBuilder::and_where_null(self, name)
+	}
+
+	fn and_where_not_null(self, name: &str) -> Self {
+		UpdateSqlBuilder::and_where_not_null(self, name)
+	}
+
+	fn and_where_eq_nullable<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		UpdateSqlBuilder::and_where_eq_nullable(self, name, val)
+	}
+
+	fn and_where_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		UpdateSqlBuilder::and_where_distinct_from(self, name, val)
+	}
+
+	fn and_where_not_distinct_from<T: 'a + SqlxBindable + Send + Sync>(self, name: &str, val: T) -> Self {
+		UpdateSqlBuilder::and_where_not_distinct_from(self, name, val)
+	}
+
+	fn and_where_select(self, name: &str, op: &'static str, select: SelectSqlBuilder<'a>) -> Self {
+		UpdateSqlBuilder::and_where_select(self, name, op, select)
+	}
+
+	fn and_where_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		UpdateSqlBuilder::and_where_exists(self, select)
+	}
+
+	fn and_where_not_exists(self, select: SelectSqlBuilder<'a>) -> Self {
+		UpdateSqlBuilder::and_where_not_exists(self, select)
+	}
+
+	fn and_where_cond(self, cond: Cond<'a>) -> Self {
+		UpdateSqlBuilder::and_where_cond(self, cond)
+	}
 }
 
-#[async_trait]
-impl<'a> SqlBuilder<'a> for UpdateSqlBuilder<'a> {
-	fn sql(&self) -> String {
+// region:    --- Builder Utils
+impl<'a> UpdateSqlBuilder<'a> {
+	/// Render the update with the placeholders starting at `idx_start` (e.g., as a CTE query).
+	/// Returns the next binding index and the sql.
+	pub(crate) fn sql_at(&self, dialect: Dialect, idx_start: usize) -> Result<(usize, String)> {
 		// SQL: UPDATE table_name SET column1 = $1, ... WHERE w1 = $2, w2 = $3 returning r1, r2;
 
-		// SQL: UPDATE table_name SET
-		let mut sql = String::from("UPDATE ");
+		let table = self.table.as_ref().ok_or(Error::MissingTable)?;
 
-		if let Some(table) = &self.table {
-			sql.push_str(&x_table_name(table));
+		// -- Check the builder state
+		// Note: The version increment is a valid set on its own.
+		if self.data.is_empty() && self.data_cols.is_empty() && self.version.is_none() {
+			return Err(Error::EmptySet {
+				table: table.to_string(),
+			});
+		}
+		if self.guard_all && self.and_wheres.is_empty() {
+			return Err(Error::UnguardedUpdate {
+				table: table.to_string(),
+			});
+		}
+		if let Some((name, _)) = &self.version {
+			let in_data = self.data.iter().any(|f| &f.name == name) || self.data_cols.iter().any(|(n, _)| n == name);
+			if in_data {
+				return Err(Error::VersionInData { name: name.to_string() });
+			}
 		}
 
-		sql.push_str("SET ");
+		// -- Check names
+		check_idents([table.as_str()])?;
+		check_idents(self.data.iter().map(|f| f.name.as_str()))?;
+		check_idents(self.data_cols.iter().flat_map(|(name, col)| [name.as_str(), col.as_str()]))?;
+		check_idents(self.version.iter().map(|(name, _)| name.as_str()))?;
+		check_idents(self.returnings.iter().flatten().map(|r| r.as_str()))?;
+
+		// SQL: WITH "name" AS (...)
+		// Note: `binding_idx` is the index for the $_idx_ in the prepared statement
+		let (mut binding_idx, mut sql) = self.ctes.sql_at(dialect, idx_start)?;
 
-		// Index for the $_idx_ in the prepared statement
-		let mut binding_idx = 1;
+		// SQL: UPDATE table_name SET
+		sql.push_str("UPDATE ");
+		sql.push_str(&format!("{} ", x_table_name(dialect, table)));
+		sql.push_str("SET ");
 
-		// TODO: Handle the case of empty data. Should we change this signature to return a Result ?
-		//       For now, just ignore this case, will fail at sql exec time
 		// SQL: column1 = $1, ...
 		let fields = &self.data;
-		let sql_set = fields
+		let mut sql_sets = fields
 			.iter()
-			.enumerate()
-			.map(|(_, f)| {
-				let mut part = format!("{} = ", x_column_name(&f.name));
-				match f.value.raw() {
-					None => {
-						part.push_str(&format!("${}", binding_idx));
-						binding_idx += 1;
-					}
-					Some(raw) => part.push_str(raw),
-				}
-				part
+			.map(|f| {
+				let value = sql_value(dialect, f.value.as_ref(), &mut binding_idx)?;
+				Ok(format!("{} = {}", x_column_name(dialect, &f.name), value))
 			})
-			.collect::<Vec<String>>()
-			.join(", ");
-		sql.push_str(&format!("{} ", sql_set));
+			.collect::<Result<Vec<String>>>()?;
+		// SQL: "status" = "s"."status", ...
+		for (name, col) in self.data_cols.iter() {
+			sql_sets.push(format!(
+				"{} = {}",
+				x_column_name(dialect, name),
+				x_column_name(dialect, col)
+			));
+		}
+		// SQL: "version" = "version" + 1
+		if let Some((name, _)) = &self.version {
+			let name = x_column_name(dialect, name);
+			sql_sets.push(format!("{} = {} + 1", name, name));
+		}
+		sql.push_str(&format!("{} ", sql_sets.join(", ")));
 
-		// SQL: WHERE w1 < $1, ...
+		// SQL: FROM "staging" AS "s", ...
+		if !self.froms.is_empty() {
+			if dialect == Dialect::MySql {
+				return Err(Error::DialectUnsupported {
+					dialect,
+					feature: "UPDATE ... FROM",
+				});
+			}
+			sql.push_str(&format!("FROM {} ", sql_tables(dialect, &self.froms)?));
+		}
+
+		// SQL: WHERE w1 < $1, ... AND "version" = $n
+		let mut sql_wheres = Vec::new();
 		if !self.and_wheres.is_empty() {
-			let sql_where = sql_where_items(&self.and_wheres, binding_idx);
-			sql.push_str(&format!("WHERE {} ", &sql_where));
-		} else if self.guard_all {
-			// For now panic, will return error later
-			panic!("FATAL - Trying to call a update without any where clause. If needed, use sqlb::update_all(table_name). ")
+			let (next_idx, sql_where) = sql_where_items(dialect, &self.and_wheres, binding_idx)?;
+			binding_idx = next_idx;
+			sql_wheres.push(sql_where);
+		}
+		if let Some((name, expected)) = &self.version {
+			let value = sql_value(dialect, expected.as_ref(), &mut binding_idx)?;
+			sql_wheres.push(format!("{} = {}", x_column_name(dialect, name), value));
+		}
+		if !sql_wheres.is_empty() {
+			sql.push_str(&format!("WHERE {} ", sql_wheres.join(" AND ")));
 		}
 
 		// SQL: RETURNING "r1", "r2", ...
 		if let Some(returnings) = &self.returnings {
-			sql.push_str(&format!("RETURNING {} ", sql_returnings(returnings)));
+			sql.push_str(&format!("RETURNING {} ", sql_returnings(dialect, returnings)?));
 		}
 
-		sql
+		Ok((binding_idx, sql))
 	}
 
-	fn vals(&'a self) -> Box<dyn Iterator<Item = &Box<dyn SqlxBindable + 'a + Send + Sync>> + 'a + Send> {
-		let iter = self.data.iter().map(|field| &field.value);
-		// FIXME needs to uncomment
-		let iter = iter.chain(self.and_wheres.iter().map(|wi| &wi.val));
-		Box::new(iter)
+	/// Collect the values to bind, in the same order as the `sql_at(..)` rendering.
+	pub(crate) fn collect_vals<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>) {
+		self.ctes.collect_vals(vals);
+		vals.extend(self.data.iter().map(|field| &field.value));
+		vals.extend(where_vals(&self.and_wheres));
+		if let Some((_, expected)) = &self.version {
+			vals.push(expected);
+		}
 	}
 
-	async fn exec<'q, E>(&'a self, db_pool: E) -> Result<u64, sqlx::Error>
+	fn stale_version_error(&self) -> Error {
+		Error::StaleVersion {
+			table: self.table.clone().unwrap_or_default(),
+		}
+	}
+}
+// endregion: --- Builder Utils
+
+#[async_trait]
+impl<'a> SqlBuilder<'a> for UpdateSqlBuilder<'a> {
+	fn try_sql_for(&self, dialect: Dialect) -> Result<String> {
+		let (_, sql) = self.sql_at(dialect, 1)?;
+		Ok(sql)
+	}
+
+	fn vals(&'a self) -> Box<dyn Iterator<Item = &'a Box<dyn SqlxBindable + 'a + Send + Sync>> + 'a + Send> {
+		let mut vals = Vec::new();
+		self.collect_vals(&mut vals);
+		Box::new(vals.into_iter())
+	}
+
+	async fn exec<'q, E>(&'a self, db_pool: E) -> Result<u64>
 	where
-		E: Executor<'q, Database = Postgres>,
+		E: Executor<'q>,
+		E::Database: SqlxDatabase,
 	{
 		Self::exec(self, db_pool).await
 	}
 
-	async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D, sqlx::Error>
+	async fn fetch_one<'e, DB, D>(&'a self, db_pool: DB) -> Result<D>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_one::<DB, D>(self, db_pool).await
 	}
 
-	async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>, sqlx::Error>
+	async fn fetch_optional<'e, DB, D>(&'a self, db_pool: DB) -> Result<Option<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_optional::<DB, D>(self, db_pool).await
 	}
 
-	async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>, sqlx::Error>
+	async fn fetch_all<'e, DB, D>(&'a self, db_pool: DB) -> Result<Vec<D>>
 	where
-		DB: Executor<'e, Database = Postgres>,
-		D: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Unpin + Send,
+		DB: Executor<'e>,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send,
 	{
 		Self::fetch_all::<DB, D>(self, db_pool).await
 	}
+
+	fn fetch_stream<'e, DB, D>(&'a self, db_pool: DB) -> BoxStream<'e, Result<D>>
+	where
+		'a: 'e,
+		Self: Sync,
+		DB: Executor<'e> + 'e,
+		DB::Database: SqlxDatabase,
+		D: for<'r> FromRow<'r, <DB::Database as sqlx::Database>::Row> + Unpin + Send + 'e,
+	{
+		Self::fetch_stream::<DB, D>(self, db_pool)
+	}
 }
@@ -1,34 +1,98 @@
+use crate::core::sql_value;
+use crate::{Dialect, Error, Result, SqlxBindable};
+
 /// Escape table name.
-/// - Surround with `"` if simple table name.
+/// - Surround with `"` (or the dialect quote) if simple table name.
 /// - If the text contains a . symbol, ensure to surround each part.
-///
-/// TODO: needs to handle the . notation (i.e., quote each side of the dot)
-pub(crate) fn x_table_name(name: &str) -> String {
+pub(crate) fn x_table_name(dialect: Dialect, name: &str) -> String {
 	if name.contains('.') {
 		name.split('.')
-			.map(|part| format!("\"{}\"", part))
+			.map(|part| dialect.quote_ident(part))
 			.collect::<Vec<String>>()
 			.join(".")
 	} else {
-		format!("\"{}\"", name)
+		dialect.quote_ident(name)
 	}
 }
 
 /// Escape column name.
-/// - Surround with `"` if simple column name.
+/// - Surround with `"` (or the dialect quote) if simple column name.
 /// - Leave column name as is if special character `(` (might need to add more)
 ///   (this allows function call like `count(*)`)
 /// - If the text contains a . symbol, ensure to surround each part.
+/// - A `*` part is left as is (e.g., `t.*` gives `"t".*`)
 ///
-pub(crate) fn x_column_name(name: &str) -> String {
-	if name.contains('(') {
+pub(crate) fn x_column_name(dialect: Dialect, name: &str) -> String {
+	if name.contains('(') || name == "*" {
 		name.to_string()
 	} else if name.contains('.') {
 		name.split('.')
-			.map(|part| format!("\"{}\"", part))
+			.map(|part| {
+				if part == "*" {
+					part.to_string()
+				} else {
+					dialect.quote_ident(part)
+				}
+			})
 			.collect::<Vec<String>>()
 			.join(".")
 	} else {
-		format!("\"{}\"", name)
+		dialect.quote_ident(name)
+	}
+}
+
+/// Check that a table or column name can be safely escaped by `x_table_name` / `x_column_name`.
+/// - Names with `(` are passed as is (e.g., `count(*)`), so they are not checked.
+/// - Otherwise, each `.` part must be non empty and cannot contain a `"` (or a NUL char).
+pub(crate) fn check_ident(name: &str) -> Result<()> {
+	if name.contains('(') {
+		return Ok(());
+	}
+
+	let valid = name.split('.').all(|part| !part.is_empty() && !part.contains(['"', '\0']));
+	if valid {
+		Ok(())
+	} else {
+		Err(Error::InvalidIdentifier { name: name.to_string() })
+	}
+}
+
+/// Render a SQL expression with the sql value of each `{}` (placeholders numbered from `binding_idx`, which is advanced).
+/// A `{{}}` is rendered as a literal `{}`.
+/// Returns `Error::ExprBindMismatch` if the number of `{}` is not the number of values to bind.
+pub(crate) fn x_expr(
+	dialect: Dialect,
+	expr: &str,
+	vals: &[Box<dyn SqlxBindable + '_ + Send + Sync>],
+	binding_idx: &mut usize,
+) -> Result<String> {
+	// split on the `{{}}` literals first, then on the `{}` placeholders of each segment
+	let segments: Vec<Vec<&str>> = expr.split("{{}}").map(|segment| segment.split("{}").collect()).collect();
+	let expected = segments.iter().map(|parts| parts.len() - 1).sum();
+	check_expr_vals(expr, expected, vals.len())?;
+
+	let mut vals = vals.iter();
+	let mut sql = String::new();
+	for (i, parts) in segments.iter().enumerate() {
+		if i > 0 {
+			sql.push_str("{}");
+		}
+		sql.push_str(parts[0]);
+		for (part, val) in parts[1..].iter().zip(&mut vals) {
+			sql.push_str(&sql_value(dialect, val.as_ref(), binding_idx)?);
+			sql.push_str(part);
+		}
+	}
+	Ok(sql)
+}
+
+pub(crate) fn check_expr_vals(expr: &str, expected: usize, actual: usize) -> Result<()> {
+	if expected != actual {
+		return Err(Error::ExprBindMismatch {
+			expr: expr.to_string(),
+			expected,
+			actual,
+		});
 	}
+	Ok(())
 }
@@ -4,6 +4,7 @@
 //! but at this point, priority is given to API ergonomics.
 //!
 
+use crate::Dialect;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
@@ -13,19 +14,90 @@ pub trait SqlxBindable: std::fmt::Debug {
 		query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
 	) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>;
 
+	/// Bind the value for SQLite (`sqlite` feature).
+	/// Note: Only called when `bindable_for(Dialect::Sqlite)` is true (e.g., `bindable!(MyType; sqlite)`).
+	#[cfg(feature = "sqlite")]
+	fn bind_query_sqlite<'q>(
+		&'q self,
+		_query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+	) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+		panic!("FATAL - Value cannot be bound for SQLite: {:?}", self)
+	}
+
+	/// Bind the value for MySQL (`mysql` feature).
+	/// Note: Only called when `bindable_for(Dialect::MySql)` is true (e.g., `bindable!(MyType; mysql)`).
+	#[cfg(feature = "mysql")]
+	fn bind_query_mysql<'q>(
+		&'q self,
+		_query: sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments>,
+	) -> sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+		panic!("FATAL - Value cannot be bound for MySQL: {:?}", self)
+	}
+
+	/// True if the value can be bound for the dialect, checked before binding (see `Error::DialectUnsupported`).
+	/// Defaults to PostgreSQL only (see the `bindable!` databases opt-in).
+	fn bindable_for(&self, dialect: Dialect) -> bool {
+		dialect == Dialect::Postgres
+	}
+
 	fn raw(&self) -> Option<&str> {
 		None
 	}
+
+	/// The SQL expression of an `Expr` value, with a `{}` for each of its bound values.
+	fn expr(&self) -> Option<&str> {
+		None
+	}
+
+	/// The bound values of an `Expr` value, in the order of its `{}` (rendered as field values, so they can be nested).
+	fn expr_vals(&self) -> &[Box<dyn SqlxBindable + '_ + Send + Sync>] {
+		&[]
+	}
+
+	/// Number of parameters bound by the value (0 for a `Raw`, the number of values for an `Expr`).
+	fn bind_count(&self) -> usize {
+		if self.raw().is_some() {
+			0
+		} else {
+			1
+		}
+	}
+
+	/// True for a SQL NULL value (e.g., `None`), used by the null-aware conditions (see `and_where_eq_nullable`).
+	fn is_null(&self) -> bool {
+		false
+	}
+
+	/// True for the column `DEFAULT` value (see `SqlDefault`), not supported by SQLite.
+	fn is_default(&self) -> bool {
+		false
+	}
 }
 
+/// A boxed builder value.
+/// Note: The builders collect their values as `&BoxedVal` (rather than `&dyn SqlxBindable`) to match
+/// the `SqlBuilder::vals()` item type, so the `Box` is borrowed as is.
+pub(crate) type BoxedVal<'a> = Box<dyn SqlxBindable + 'a + Send + Sync>;
+
+/// Implement `SqlxBindable` for types bound by clone (e.g., `sqlb::bindable!(MyEnum)`).
+///
+/// The PostgreSQL binding is always implemented. The SQLite and MySQL bindings are opt-in, listed after a `;`
+/// (e.g., `sqlb::bindable!(MyEnum; sqlite, mysql)`), and require the sqlx `Type` and `Encode` of these databases.
+/// Note: A listed database is only bound when the matching sqlb feature is enabled.
 #[macro_export]
 macro_rules! bindable {
-    ($($t:ty),*) => {
+    (@impl $dbs:tt $($t:ty),*) => {
         $(impl $crate::SqlxBindable for $t {
             fn bind_query<'q>(&self, query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
                 let query = query.bind(self.clone());
                 query
             }
+
+            fn bindable_for(&self, dialect: $crate::Dialect) -> bool {
+                $crate::__bindable_for!(dialect, $dbs)
+            }
+
+            $crate::__bind_query_dbs!(clone, $dbs);
         }
 
         impl $crate::SqlxBindable for &$t {
@@ -33,21 +105,42 @@ macro_rules! bindable {
                 let query = query.bind(<$t>::clone(self));
                 query
             }
+
+            fn bindable_for(&self, dialect: $crate::Dialect) -> bool {
+                $crate::__bindable_for!(dialect, $dbs)
+            }
+
+            $crate::__bind_query_dbs!(deref_clone, $dbs);
         }
 
         )*
     };
+    ($($t:ty),* ; $($db:ident),*) => {
+        $crate::bindable!(@impl [$($db),*] $($t),*);
+    };
+    ($($t:ty),*) => {
+        $crate::bindable!(@impl [] $($t),*);
+    };
 }
 
+/// Implement `SqlxBindable` for types bound as their `to_string()` (e.g., `sqlb::bindable_to_string!(MyId)`).
+///
+/// Same databases opt-in as `bindable!` (e.g., `sqlb::bindable_to_string!(MyId; sqlite, mysql)`).
 #[macro_export]
 macro_rules! bindable_to_string {
-	($($t:ident),*) => {
+	(@impl $dbs:tt $($t:ident),*) => {
 		$(
 		impl $crate::SqlxBindable for $t {
 			fn bind_query<'q>(&self, query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
 				let query = query.bind(self.to_string());
 				query
 			}
+
+			fn bindable_for(&self, dialect: $crate::Dialect) -> bool {
+				$crate::__bindable_for!(dialect, $dbs)
+			}
+
+			$crate::__bind_query_dbs!(to_string, $dbs);
 		}
 
 		impl $crate::SqlxBindable for &$t {
@@ -55,13 +148,124 @@ macro_rules! bindable_to_string {
 				let query = query.bind(self.to_string());
 				query
 			}
+
+			fn bindable_for(&self, dialect: $crate::Dialect) -> bool {
+				$crate::__bindable_for!(dialect, $dbs)
+			}
+
+			$crate::__bind_query_dbs!(to_string, $dbs);
 		}
 		)*
 	};
+	($($t:ident),* ; $($db:ident),*) => {
+		$crate::bindable_to_string!(@impl [$($db),*] $($t),*);
+	};
+	($($t:ident),*) => {
+		$crate::bindable_to_string!(@impl [] $($t),*);
+	};
+}
+
+// region:    --- Dialect Bind Helpers
+// NOTE: The `bindable!` macros expand in the user crate, so the cfg of the sqlb features are evaluated here,
+//       with one definition per feature state.
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bindable_for {
+	($dialect:ident, [$($db:ident),*]) => {
+		$dialect == $crate::Dialect::Postgres $(|| $dialect == $crate::__dialect!($db))*
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __dialect {
+	(sqlite) => {
+		$crate::Dialect::Sqlite
+	};
+	(mysql) => {
+		$crate::Dialect::MySql
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bind_query_dbs {
+	($conv:ident, [$($db:ident),*]) => {
+		$($crate::__bind_query_db!($db, $conv);)*
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bind_query_db {
+	(sqlite, $conv:ident) => {
+		$crate::__bind_query_sqlite!($conv);
+	};
+	(mysql, $conv:ident) => {
+		$crate::__bind_query_mysql!($conv);
+	};
+}
+
+#[cfg(feature = "sqlite")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bind_query_sqlite {
+	($conv:ident) => {
+		fn bind_query_sqlite<'q>(
+			&'q self,
+			query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+		) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+			query.bind($crate::__bind_conv!(self, $conv))
+		}
+	};
+}
+
+#[cfg(not(feature = "sqlite"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bind_query_sqlite {
+	($conv:ident) => {};
+}
+
+#[cfg(feature = "mysql")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bind_query_mysql {
+	($conv:ident) => {
+		fn bind_query_mysql<'q>(
+			&'q self,
+			query: sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments>,
+		) -> sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+			query.bind($crate::__bind_conv!(self, $conv))
+		}
+	};
+}
+
+#[cfg(not(feature = "mysql"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bind_query_mysql {
+	($conv:ident) => {};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bind_conv {
+	($self:ident, clone) => {
+		$self.clone()
+	};
+	($self:ident, deref_clone) => {
+		(*$self).clone()
+	};
+	($self:ident, to_string) => {
+		$self.to_string()
+	};
 }
+// endregion: --- Dialect Bind Helpers
 
 // Bind the string types
-bindable_to_string!(String, str);
+bindable_to_string!(String, str; sqlite, mysql);
 
 impl<T> SqlxBindable for Option<T>
 where
@@ -76,15 +280,83 @@ where
 		let query = query.bind(self.clone());
 		query
 	}
+
+	#[cfg(feature = "sqlite")]
+	fn bind_query_sqlite<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+	) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+		match self {
+			Some(val) => val.bind_query_sqlite(query),
+			// Note: SQLite values are dynamically typed, so the NULL type does not matter.
+			None => query.bind(None::<i64>),
+		}
+	}
+
+	#[cfg(feature = "mysql")]
+	fn bind_query_mysql<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments>,
+	) -> sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+		match self {
+			Some(val) => val.bind_query_mysql(query),
+			None => query.bind(None::<i64>),
+		}
+	}
+
+	// Note: A `None` is bound as a typeless NULL for SQLite and MySQL.
+	fn bindable_for(&self, dialect: Dialect) -> bool {
+		match self {
+			Some(val) => val.bindable_for(dialect),
+			None => true,
+		}
+	}
+
+	fn is_null(&self) -> bool {
+		self.is_none()
+	}
 }
 
 // Bind the boolean
-bindable!(bool);
+bindable!(bool; sqlite, mysql);
 // Bind the numbers
 // NOTE: Skipping u8, u16, u64 since not mapped by sqlx to postgres.
-bindable!(i8, i16, i32, i64, f32, f64);
+bindable!(i8, i16, i32, i64, f32, f64; sqlite, mysql);
+
+bindable!(Uuid, OffsetDateTime; sqlite, mysql);
 
-bindable!(Uuid, OffsetDateTime);
+// region:    --- Array Values
+// NOTE: Bound as a PostgreSQL array, typically for `= ANY($1)` (see `and_where_in`).
+//       Not bindable for SQLite and MySQL (no array type).
+
+impl<T> SqlxBindable for Vec<T>
+where
+	T: std::fmt::Debug + Clone + Send,
+	Vec<T>: for<'r> sqlx::Encode<'r, sqlx::Postgres> + sqlx::Type<sqlx::Postgres>,
+{
+	fn bind_query<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+	) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+		let query = query.bind(self.clone());
+		query
+	}
+}
+
+impl<T> SqlxBindable for &[T]
+where
+	T: std::fmt::Debug + Sync,
+	for<'r> &'r [T]: sqlx::Encode<'r, sqlx::Postgres> + sqlx::Type<sqlx::Postgres>,
+{
+	fn bind_query<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+	) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+		let query = query.bind(*self);
+		query
+	}
+}
+// endregion: --- Array Values
 
 // region:    --- Raw Value
 
@@ -93,7 +365,7 @@ bindable!(Uuid, OffsetDateTime);
 mod chrono_support {
 	use chrono::{NaiveDateTime, NaiveDate, NaiveTime, DateTime, Utc};
 
-	bindable!(NaiveDateTime, NaiveDate, NaiveTime, DateTime<Utc>);
+	bindable!(NaiveDateTime, NaiveDate, NaiveTime, DateTime<Utc>; sqlite, mysql);
 }
 // endregion: --- chrono support
 
@@ -102,7 +374,7 @@ mod chrono_support {
 mod json {
     use serde_json::Value;
 
-	bindable!(Value);
+	bindable!(Value; sqlite, mysql);
 }
 // endregion: --- json support
 
@@ -111,7 +383,36 @@ mod json {
 mod decimal {
     use rust_decimal::Decimal;
 
-	bindable!(Decimal);
+	// NOTE: No SQLite mapping for Decimal in sqlx.
+	impl crate::SqlxBindable for Decimal {
+		fn bind_query<'q>(
+			&'q self,
+			query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+		) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+			query.bind(*self)
+		}
+
+		fn bindable_for(&self, dialect: crate::Dialect) -> bool {
+			crate::__bindable_for!(dialect, [mysql])
+		}
+
+		crate::__bind_query_mysql!(clone);
+	}
+
+	impl crate::SqlxBindable for &Decimal {
+		fn bind_query<'q>(
+			&'q self,
+			query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+		) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+			query.bind(**self)
+		}
+
+		fn bindable_for(&self, dialect: crate::Dialect) -> bool {
+			crate::__bindable_for!(dialect, [mysql])
+		}
+
+		crate::__bind_query_mysql!(deref_clone);
+	}
 }
 // endregion: --- decimal support
 
@@ -127,12 +428,164 @@ impl SqlxBindable for Raw {
 		query
 	}
 
+	#[cfg(feature = "sqlite")]
+	fn bind_query_sqlite<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+	) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+		query
+	}
+
+	#[cfg(feature = "mysql")]
+	fn bind_query_mysql<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments>,
+	) -> sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+		query
+	}
+
+	fn bindable_for(&self, _dialect: Dialect) -> bool {
+		true
+	}
+
 	fn raw(&self) -> Option<&str> {
 		Some(self.0)
 	}
 }
 // endregion: --- Raw Value
 
+// region:    --- Default Value
+
+/// Marker value rendering the column `DEFAULT` (e.g., `("ctime", sqlb::SqlDefault).into()`) in an insert or update.
+/// Note: SQLite does not support `DEFAULT` values (`Error::DialectUnsupported`, omit the field instead).
+#[derive(Debug)]
+pub struct SqlDefault;
+
+impl SqlxBindable for SqlDefault {
+	// nothing to bind, the value is the `DEFAULT` keyword
+	fn bind_query<'q>(
+		&self,
+		query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+	) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+		query
+	}
+
+	#[cfg(feature = "sqlite")]
+	fn bind_query_sqlite<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+	) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+		query
+	}
+
+	#[cfg(feature = "mysql")]
+	fn bind_query_mysql<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments>,
+	) -> sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+		query
+	}
+
+	fn bindable_for(&self, _dialect: Dialect) -> bool {
+		true
+	}
+
+	fn raw(&self) -> Option<&str> {
+		Some("DEFAULT")
+	}
+
+	fn is_default(&self) -> bool {
+		true
+	}
+}
+// endregion: --- Default Value
+
+// region:    --- Expr Value
+
+/// SQL expression value, with a `{}` for each value bound with `.bind(..)`, for a field value, a where value,
+/// an order by (see `sqlb::order_expr(..)`), or a window function (see `WindowFn::expr(..)`).
+///
+/// e.g., `("counter", Expr::new("\"counter\" + {}").bind(1)).into()` for `"counter" = "counter" + $1`
+///
+/// Note: The expression is passed through as is (the values are always bound), so it must not come from user input.
+/// Note: The bound values can be `Raw` or nested `Expr` values (e.g., `expr("upper({})").bind(expr("lower({})").bind("x"))`).
+/// Note: A `{{}}` is a literal `{}` (e.g., `expr("coalesce(\"tags\", '{{}}')")` for `coalesce("tags", '{}')`).
+#[derive(Debug)]
+pub struct Expr<'a> {
+	sql: String,
+	vals: Vec<Box<dyn SqlxBindable + 'a + Send + Sync>>,
+}
+
+/// Create an `Expr` value (same as `Expr::new(..)`).
+///
+/// e.g., `sqlb::expr("lower({})").bind("Title 01")`
+pub fn expr<'a>(sql: &str) -> Expr<'a> {
+	Expr::new(sql)
+}
+
+impl<'a> Expr<'a> {
+	pub fn new(sql: &str) -> Self {
+		Expr {
+			sql: sql.to_string(),
+			vals: Vec::new(),
+		}
+	}
+
+	/// Bind a value for the next `{}` of the expression.
+	pub fn bind<T: 'a + SqlxBindable + Send + Sync>(mut self, val: T) -> Self {
+		self.vals.push(Box::new(val));
+		self
+	}
+
+	/// Values to bind, in the expression order (for the `Expr` of the order bys and window functions).
+	pub(crate) fn collect_vals<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>) {
+		vals.extend(self.vals.iter());
+	}
+}
+
+impl<'a> SqlxBindable for Expr<'a> {
+	// bind the inner values, in the expression order
+	fn bind_query<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+	) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+		self.vals.iter().fold(query, |query, val| val.bind_query(query))
+	}
+
+	#[cfg(feature = "sqlite")]
+	fn bind_query_sqlite<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+	) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+		self.vals.iter().fold(query, |query, val| val.bind_query_sqlite(query))
+	}
+
+	#[cfg(feature = "mysql")]
+	fn bind_query_mysql<'q>(
+		&'q self,
+		query: sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments>,
+	) -> sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+		self.vals.iter().fold(query, |query, val| val.bind_query_mysql(query))
+	}
+
+	fn bindable_for(&self, dialect: Dialect) -> bool {
+		self.vals.iter().all(|val| val.bindable_for(dialect))
+	}
+
+	fn expr(&self) -> Option<&str> {
+		Some(&self.sql)
+	}
+
+	fn expr_vals(&self) -> &[Box<dyn SqlxBindable + '_ + Send + Sync>] {
+		&self.vals
+	}
+
+	fn bind_count(&self) -> usize {
+		self.vals.iter().map(|val| val.bind_count()).sum()
+	}
+}
+// endregion: --- Expr Value
+
 #[cfg(test)]
 mod tests {
 	use crate::Field;
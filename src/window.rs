@@ -0,0 +1,256 @@
+//! Window functions for the `SelectSqlBuilder` columns and order bys
+//! (e.g., `row_number() OVER (PARTITION BY "project_id" ORDER BY "ctime" DESC)`), and the named `WINDOW` definitions.
+
+use crate::core::sql_value;
+use crate::order::OrderBy;
+use crate::utils::{check_ident, x_column_name};
+use crate::val::BoxedVal;
+use crate::{Dialect, Expr, Result};
+
+/// Create a window definition, for `WindowFn::over(..)` and `SelectSqlBuilder::window(..)`.
+///
+/// e.g., `sqlb::window().partition_by(&["project_id"]).order_bys(&["!ctime"])`
+pub fn window<'a>() -> Window<'a> {
+	Window {
+		partition_bys: Vec::new(),
+		order_bys: Vec::new(),
+		frame: None,
+	}
+}
+
+/// Window definition, `PARTITION BY ... ORDER BY ... [frame]`.
+pub struct Window<'a> {
+	partition_bys: Vec<String>,
+	order_bys: Vec<OrderBy<'a>>,
+	frame: Option<&'static str>,
+}
+
+impl<'a> Window<'a> {
+	pub fn partition_by(mut self, names: &[&str]) -> Self {
+		self.partition_bys = names.iter().map(|s| s.to_string()).collect();
+		self
+	}
+
+	/// Order by columns (`!` prefix for `DESC`).
+	pub fn order_bys(mut self, odrs: &[&str]) -> Self {
+		self.order_bys = odrs.iter().map(|o| OrderBy::from(*o)).collect();
+		self
+	}
+
+	/// Add an order by item (e.g., `sqlb::order("ctime").nulls_last()`) after the current ones.
+	pub fn add_order_by(mut self, odr: impl Into<OrderBy<'a>>) -> Self {
+		self.order_bys.push(odr.into());
+		self
+	}
+
+	/// Frame clause, passed through as is (e.g., `"ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW"`).
+	pub fn frame(mut self, frame: &'static str) -> Self {
+		self.frame = Some(frame);
+		self
+	}
+
+	// SQL: PARTITION BY "project_id" ORDER BY "ctime" DESC ROWS BETWEEN ...
+	pub(crate) fn sql(&self, dialect: Dialect, binding_idx: &mut usize) -> Result<String> {
+		let mut parts = Vec::new();
+
+		if !self.partition_bys.is_empty() {
+			for name in self.partition_bys.iter() {
+				check_ident(name)?;
+			}
+			let names: Vec<String> = self.partition_bys.iter().map(|n| x_column_name(dialect, n)).collect();
+			parts.push(format!("PARTITION BY {}", names.join(", ")));
+		}
+
+		if !self.order_bys.is_empty() {
+			let order_bys = self
+				.order_bys
+				.iter()
+				.map(|o| o.sql(dialect, binding_idx))
+				.collect::<Result<Vec<String>>>()?;
+			parts.push(format!("ORDER BY {}", order_bys.join(", ")));
+		}
+
+		if let Some(frame) = self.frame {
+			parts.push(frame.to_string());
+		}
+
+		Ok(parts.join(" "))
+	}
+
+	/// Values to bind, in the `sql(..)` order.
+	pub(crate) fn collect_vals<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>) {
+		for order_by in self.order_bys.iter() {
+			order_by.collect_vals(vals);
+		}
+	}
+}
+
+/// Window function for the `SelectSqlBuilder` columns (see `window_fn(..)`) and order bys (see `sqlb::order_window(..)`).
+///
+/// e.g., `WindowFn::row_number().over(sqlb::window().partition_by(&["project_id"]).order_bys(&["id"])).alias("rn")`
+///
+/// Note: Without `over(..)` or `over_named(..)`, the window is the whole result (i.e., `OVER ()`).
+pub struct WindowFn<'a> {
+	func: WindowFunc<'a>,
+	over: Option<Over<'a>>,
+	alias: Option<String>,
+}
+
+enum WindowFunc<'a> {
+	/// Function name, with the column argument (None for no argument, or the `*` of `count(*)`).
+	Call(&'static str, Option<String>),
+	/// Function call expression (see `WindowFn::expr(..)`).
+	Expr(Expr<'a>),
+}
+
+enum Over<'a> {
+	Window(Window<'a>),
+	Named(String),
+}
+
+// region:    --- Constructors
+impl<'a> WindowFn<'a> {
+	fn new(func: WindowFunc<'a>) -> Self {
+		WindowFn {
+			func,
+			over: None,
+			alias: None,
+		}
+	}
+
+	fn call(name: &'static str, column: Option<&str>) -> Self {
+		Self::new(WindowFunc::Call(name, column.map(|c| c.to_string())))
+	}
+
+	/// `row_number()`
+	pub fn row_number() -> Self {
+		Self::call("row_number", None)
+	}
+
+	/// `rank()`
+	pub fn rank() -> Self {
+		Self::call("rank", None)
+	}
+
+	/// `dense_rank()`
+	pub fn dense_rank() -> Self {
+		Self::call("dense_rank", None)
+	}
+
+	/// `count(*)`
+	pub fn count_all() -> Self {
+		Self::call("count", Some("*"))
+	}
+
+	pub fn count(column: &str) -> Self {
+		Self::call("count", Some(column))
+	}
+
+	pub fn sum(column: &str) -> Self {
+		Self::call("sum", Some(column))
+	}
+
+	pub fn avg(column: &str) -> Self {
+		Self::call("avg", Some(column))
+	}
+
+	pub fn min(column: &str) -> Self {
+		Self::call("min", Some(column))
+	}
+
+	pub fn max(column: &str) -> Self {
+		Self::call("max", Some(column))
+	}
+
+	pub fn lag(column: &str) -> Self {
+		Self::call("lag", Some(column))
+	}
+
+	pub fn lead(column: &str) -> Self {
+		Self::call("lead", Some(column))
+	}
+
+	pub fn first_value(column: &str) -> Self {
+		Self::call("first_value", Some(column))
+	}
+
+	pub fn last_value(column: &str) -> Self {
+		Self::call("last_value", Some(column))
+	}
+
+	/// Function call expression (see `Expr`).
+	///
+	/// e.g., `WindowFn::expr(sqlb::expr("lag(\"title\", {}, {})").bind(2).bind("none"))`
+	pub fn expr(expr: Expr<'a>) -> Self {
+		Self::new(WindowFunc::Expr(expr))
+	}
+}
+// endregion: --- Constructors
+
+impl<'a> WindowFn<'a> {
+	/// `OVER (PARTITION BY ... ORDER BY ...)`
+	pub fn over(mut self, window: Window<'a>) -> Self {
+		self.over = Some(Over::Window(window));
+		self
+	}
+
+	/// `OVER "name"`, for a window defined with `SelectSqlBuilder::window(name, ..)`.
+	pub fn over_named(mut self, name: &str) -> Self {
+		self.over = Some(Over::Named(name.to_string()));
+		self
+	}
+
+	/// Column alias (e.g., `row_number() OVER (...) AS "rn"`), ignored in an order by.
+	pub fn alias(mut self, alias: &str) -> Self {
+		self.alias = Some(alias.to_string());
+		self
+	}
+
+	// SQL: rank() OVER (PARTITION BY "project_id" ORDER BY "ctime" DESC)
+	pub(crate) fn sql(&self, dialect: Dialect, binding_idx: &mut usize) -> Result<String> {
+		let sql_func = match &self.func {
+			WindowFunc::Call(name, column) => {
+				let column = match column {
+					Some(column) => {
+						check_ident(column)?;
+						x_column_name(dialect, column)
+					}
+					None => String::new(),
+				};
+				format!("{}({})", name, column)
+			}
+			WindowFunc::Expr(expr) => sql_value(dialect, expr, binding_idx)?,
+		};
+
+		let sql_over = match &self.over {
+			Some(Over::Window(window)) => format!("({})", window.sql(dialect, binding_idx)?),
+			Some(Over::Named(name)) => {
+				check_ident(name)?;
+				dialect.quote_ident(name)
+			}
+			None => "()".to_string(),
+		};
+
+		Ok(format!("{} OVER {}", sql_func, sql_over))
+	}
+
+	// SQL: row_number() OVER (...) AS "rn"
+	pub(crate) fn sql_column(&self, dialect: Dialect, binding_idx: &mut usize) -> Result<String> {
+		let mut sql = self.sql(dialect, binding_idx)?;
+		if let Some(alias) = &self.alias {
+			check_ident(alias)?;
+			sql.push_str(&format!(" AS {}", x_column_name(dialect, alias)));
+		}
+		Ok(sql)
+	}
+
+	/// Values to bind, in the `sql(..)` order.
+	pub(crate) fn collect_vals<'s>(&'s self, vals: &mut Vec<&'s BoxedVal<'a>>) {
+		if let WindowFunc::Expr(expr) = &self.func {
+			expr.collect_vals(vals);
+		}
+		if let Some(Over::Window(window)) = &self.over {
+			window.collect_vals(vals);
+		}
+	}
+}
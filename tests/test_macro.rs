@@ -114,3 +114,20 @@ pub fn test_bindable_generic() -> Result<()> {
 
 	Ok(())
 }
+
+#[test]
+pub fn test_bindable_dbs() -> Result<()> {
+	use sqlb::{Dialect, SqlxBindable};
+
+	#[derive(Debug, Clone, sqlx::Type)]
+	#[sqlx(transparent)]
+	pub struct ClientId(i64);
+
+	sqlb::bindable!(ClientId; sqlite);
+
+	assert!(ClientId(1).bindable_for(Dialect::Postgres));
+	assert!(ClientId(1).bindable_for(Dialect::Sqlite));
+	assert!(!ClientId(1).bindable_for(Dialect::MySql));
+
+	Ok(())
+}
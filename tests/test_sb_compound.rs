@@ -0,0 +1,154 @@
+mod utils;
+
+use serial_test::serial;
+use sqlb::{Dialect, SqlBuilder};
+use std::error::Error;
+use utils::init_db;
+
+#[test]
+fn sb_compound_ok_renumbered_sql() {
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where_eq("project_id", 1)
+		.union_all(sqlb::select().table("project").columns(&["name"]).and_where_eq("id", 2))
+		.except(
+			sqlb::select()
+				.table("todo")
+				.columns(&["title"])
+				.and_where_eq("description", "x")
+				.limit(1),
+		)
+		.add_order_by(sqlb::order_expr(sqlb::expr(r#"length("title") - {}"#).bind(3)))
+		.limit(10)
+		.offset(5);
+
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT "title" FROM "todo" WHERE "project_id" = $1 UNION ALL SELECT "name" FROM "project" WHERE "id" = $2 EXCEPT (SELECT "title" FROM "todo" WHERE "description" = $3 LIMIT 1) ORDER BY length("title") - $4 LIMIT 10 OFFSET 5 "#
+	);
+	assert_eq!(sb.vals().count(), 4);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_compound_ok_union_all() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	for name in ["search project A", "other project"] {
+		sqlb::insert()
+			.table("project")
+			.data(vec![("name", name).into()])
+			.exec(&db_pool)
+			.await?;
+	}
+	for title in ["search todo 01", "search todo 02", "other todo"] {
+		sqlb::insert()
+			.table("todo")
+			.data(vec![("title", title).into()])
+			.exec(&db_pool)
+			.await?;
+	}
+
+	// -- Exec
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where("title", "LIKE", "search%")
+		.union_all(
+			sqlb::select()
+				.table("project")
+				.columns(&["name"])
+				.and_where("name", "LIKE", "search%"),
+		)
+		.order_by("!title")
+		.limit(2);
+	let titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+	let count = sb.fetch_count(&db_pool).await?;
+	let page = sb.fetch_page_over::<_, (String,)>(&db_pool, 2, 2).await?;
+
+	// -- Check
+	assert_eq!(
+		titles,
+		vec![("search todo 02".to_string(),), ("search todo 01".to_string(),)]
+	);
+	assert_eq!(count, 2);
+	assert_eq!(page.items, vec![("search project A".to_string(),)]);
+	assert_eq!(page.total, 3);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_compound_ok_intersect_except() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	for title in ["title 01", "title 02", "title 03"] {
+		sqlb::insert()
+			.table("todo")
+			.data(vec![("title", title).into()])
+			.exec(&db_pool)
+			.await?;
+	}
+	let titles_sb = |op: &'static str, title: &'static str| {
+		sqlb::select().table("todo").columns(&["title"]).and_where("title", op, title)
+	};
+
+	// -- Exec
+	let intersect: Vec<(String,)> = titles_sb(">=", "title 02")
+		.intersect(titles_sb("<=", "title 02"))
+		.fetch_all(&db_pool)
+		.await?;
+	let except: Vec<(String,)> = titles_sb(">=", "title 01")
+		.except(titles_sb("=", "title 02"))
+		.order_by("title")
+		.fetch_all(&db_pool)
+		.await?;
+
+	// -- Check
+	assert_eq!(intersect, vec![("title 02".to_string(),)]);
+	assert_eq!(except, vec![("title 01".to_string(),), ("title 03".to_string(),)]);
+
+	Ok(())
+}
+
+#[test]
+fn sb_compound_err_sqlite_intersect_all() {
+	let sb = sqlb::select().table("todo").intersect_all(sqlb::select().table("project"));
+	let res = sb.try_sql_for(Dialect::Sqlite);
+
+	assert!(
+		matches!(res, Err(sqlb::Error::DialectUnsupported { .. })),
+		"should be DialectUnsupported error, but was: {:?}",
+		res
+	);
+}
+
+#[test]
+fn sb_compound_err_lock() {
+	// lock on the compound select
+	let sb = sqlb::select()
+		.table("todo")
+		.union(sqlb::select().table("todo_archive"))
+		.for_update();
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::LockWithCompound)),
+		"should be LockWithCompound error, but was: {:?}",
+		res
+	);
+
+	// lock on a compound branch
+	let sb = sqlb::select()
+		.table("todo")
+		.union(sqlb::select().table("todo_archive").for_update());
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::LockWithCompound)),
+		"should be LockWithCompound error, but was: {:?}",
+		res
+	);
+}
@@ -0,0 +1,234 @@
+mod utils;
+
+use crate::utils::{util_fetch_all_todos, util_insert_todos};
+use serial_test::serial;
+use sqlb::{Cond, Raw, SqlBuilder, Whereable};
+use std::error::Error;
+use utils::init_db;
+
+#[test]
+fn sb_cond_ok_nested_sql() {
+	let sb = sqlb::select()
+		.table("todo")
+		.and_where_eq("a", 1)
+		.and_where_cond(Cond::eq("b", 2).or(Cond::op("c", ">", 3).and(Cond::not(Cond::eq("d", 4)))))
+		.and_where_eq("e", 5);
+
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT * FROM "todo" WHERE "a" = $1 AND ("b" = $2 OR ("c" > $3 AND NOT ("d" = $4))) AND "e" = $5 "#
+	);
+	assert_eq!(sb.vals().count(), 5);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_cond_ok_select_or() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	util_insert_todos(&db_pool, &["title 01", "title 02", "title 03"]).await?;
+
+	// -- Exec
+	let cond = Cond::any(vec![Cond::eq("title", "title 01"), Cond::eq("title", "title 03")]);
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where_cond(cond)
+		.order_by("title");
+	let titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(titles, vec![("title 01".to_string(),), ("title 03".to_string(),)]);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_cond_ok_update_delete_whereable() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let ids = util_insert_todos(&db_pool, &["title 01", "title 02", "title 03"]).await?;
+
+	// -- Exec
+	// Note: Uses the Whereable trait to check it works the same way across builders.
+	fn not_id<'a, W: Whereable<'a>>(sb: W, id: i64) -> W {
+		sb.and_where_cond(Cond::not(Cond::eq("id", id)))
+	}
+	let fields = vec![("title", "new title").into()];
+	let sb = not_id(sqlb::update().table("todo").data(fields), ids[0]);
+	let updated = sb.exec(&db_pool).await?;
+	let sb = not_id(sqlb::delete().table("todo"), ids[0]).and_where_cond(Cond::eq("id", ids[1]).or(Cond::eq("id", -1)));
+	let deleted = sb.exec(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(updated, 2);
+	assert_eq!(deleted, 1);
+	let todos = util_fetch_all_todos(&db_pool).await?;
+	let titles: Vec<&str> = todos.iter().map(|t| t.title.as_str()).collect();
+	assert_eq!(titles, vec!["new title", "title 01"]);
+
+	Ok(())
+}
+
+#[test]
+fn sb_cond_ok_in_sql() {
+	let sb = sqlb::select()
+		.table("todo")
+		.and_where_in("id", vec![1i64, 2, 3])
+		.and_where_not_in("title", &["a", "b"][..])
+		.and_where_eq("e", 5);
+
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT * FROM "todo" WHERE "id" = ANY($1) AND "title" <> ALL($2) AND "e" = $3 "#
+	);
+	assert_eq!(sb.vals().count(), 3);
+}
+
+#[test]
+fn sb_cond_ok_in_expr_sql() {
+	let sb = sqlb::select()
+		.table("todo")
+		.and_where_eq("e", 5)
+		.and_where_in(
+			"id",
+			sqlb::expr("string_to_array({}, {})::bigint[]").bind("1,2").bind(","),
+		)
+		.and_where_not_in("title", Raw("ARRAY['a', 'b']"))
+		.and_where_eq("f", 6);
+
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT * FROM "todo" WHERE "e" = $1 AND "id" = ANY(string_to_array($2, $3)::bigint[]) AND "title" <> ALL(ARRAY['a', 'b']) AND "f" = $4 "#
+	);
+	assert_eq!(sb.vals().count(), 4);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_cond_ok_select_in() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let ids = util_insert_todos(&db_pool, &["title 01", "title 02", "title 03"]).await?;
+
+	// -- Exec
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where_in("id", vec![ids[0], ids[2]])
+		.order_by("title");
+	let in_titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+	let not_in_ids = [ids[0], ids[2]];
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where_not_in("id", &not_in_ids[..]);
+	let not_in_titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where_in("id", sqlb::expr("ARRAY[{}, {}]").bind(ids[1]).bind(ids[2]))
+		.order_by("title");
+	let in_expr_titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(in_titles, vec![("title 01".to_string(),), ("title 03".to_string(),)]);
+	assert_eq!(not_in_titles, vec![("title 02".to_string(),)]);
+	assert_eq!(
+		in_expr_titles,
+		vec![("title 02".to_string(),), ("title 03".to_string(),)]
+	);
+
+	Ok(())
+}
+
+#[test]
+fn sb_cond_ok_null_sql() {
+	let sb = sqlb::select()
+		.table("todo")
+		.and_where_null("description")
+		.and_where_not_null("title")
+		.and_where_eq_nullable("desc", None::<String>)
+		.and_where_eq_nullable("project_id", Some(1i64))
+		.and_where_distinct_from("status", "open");
+
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT * FROM "todo" WHERE "description" IS NULL AND "title" IS NOT NULL AND "desc" IS NULL AND "project_id" = $1 AND "status" IS DISTINCT FROM $2 "#
+	);
+	assert_eq!(sb.vals().count(), 2);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_cond_ok_select_null() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let ids = util_insert_todos(&db_pool, &["title 01", "title 02", "title 03"]).await?;
+	let fields = vec![("description", "desc 01").into()];
+	sqlb::update()
+		.table("todo")
+		.data(fields)
+		.and_where_eq("id", ids[0])
+		.exec(&db_pool)
+		.await?;
+
+	// -- Exec
+	let fetch_titles = |sb: sqlb::SelectSqlBuilder<'static>| {
+		let db_pool = db_pool.clone();
+		async move {
+			let titles: Vec<(String,)> = sb.columns(&["title"]).order_by("title").fetch_all(&db_pool).await?;
+			Ok::<_, sqlb::Error>(titles.into_iter().map(|t| t.0).collect::<Vec<String>>())
+		}
+	};
+	let null_titles = fetch_titles(
+		sqlb::select()
+			.table("todo")
+			.and_where_eq_nullable("description", None::<String>),
+	)
+	.await?;
+	let some_titles = fetch_titles(
+		sqlb::select()
+			.table("todo")
+			.and_where_eq_nullable("description", Some("desc 01".to_string())),
+	)
+	.await?;
+	let not_null_titles = fetch_titles(sqlb::select().table("todo").and_where_not_null("description")).await?;
+	let distinct_titles =
+		fetch_titles(sqlb::select().table("todo").and_where_distinct_from("description", "desc 01")).await?;
+
+	// -- Check
+	assert_eq!(null_titles, vec!["title 02", "title 03"]);
+	assert_eq!(some_titles, vec!["title 01"]);
+	assert_eq!(not_null_titles, vec!["title 01"]);
+	assert_eq!(distinct_titles, vec!["title 02", "title 03"]);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_cond_ok_raw_and_cols() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	util_insert_todos(&db_pool, &["title 01", "title 02"]).await?;
+
+	// -- Exec
+	let sb = sqlb::select()
+		.table_as("todo", "t")
+		.columns(&["title"])
+		.and_where("t.title", "<>", Raw("''"))
+		.and_where_cond(Cond::cols("t.title", "<>", "t.description").or(Cond::is_null("t.description")))
+		.and_where_eq("t.title", "title 02");
+	let titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT "title" FROM "todo" AS "t" WHERE "t"."title" <> '' AND ("t"."title" <> "t"."description" OR "t"."description" IS NULL) AND "t"."title" = $1 "#
+	);
+	assert_eq!(titles, vec![("title 02".to_string(),)]);
+
+	Ok(())
+}
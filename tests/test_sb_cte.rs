@@ -0,0 +1,208 @@
+mod utils;
+
+use crate::utils::util_insert_many_todos;
+use serial_test::serial;
+use sqlb::SqlBuilder;
+use std::error::Error;
+use utils::init_db;
+
+#[test]
+fn sb_cte_ok_renumbered_sql() {
+	let sb = sqlb::update()
+		.with(
+			"p",
+			sqlb::select().table("project").columns(&["id"]).and_where_eq("name", "a"),
+		)
+		.with(
+			"d",
+			sqlb::delete().table("todo").and_where_eq("title", "b").returning(&["id"]),
+		)
+		.table("todo")
+		.data(vec![("description", "c").into()])
+		.and_where_select("project_id", "IN", sqlb::select().table("p").columns(&["id"]))
+		.and_where_eq("title", "d");
+
+	assert_eq!(
+		sb.sql(),
+		r#"WITH "p" AS (SELECT "id" FROM "project" WHERE "name" = $1), "d" AS (DELETE FROM "todo" WHERE "title" = $2 RETURNING "id") UPDATE "todo" SET "description" = $3 WHERE "project_id" IN (SELECT "id" FROM "p") AND "title" = $4 "#
+	);
+	assert_eq!(sb.vals().count(), 4);
+}
+
+// Note: One recursive CTE makes the whole `WITH` clause `WITH RECURSIVE` (see `sb_cte_ok_recursive_tree` for a query).
+#[test]
+fn sb_cte_ok_recursive_keyword_sql() {
+	let sb = sqlb::select()
+		.with("a", sqlb::select().table("todo").and_where_eq("id", 1))
+		.with_recursive(
+			"b",
+			sqlb::select().table("a").columns(&["id"]).and_where_eq("title", "t"),
+		)
+		.table("b");
+
+	assert_eq!(
+		sb.sql(),
+		r#"WITH RECURSIVE "a" AS (SELECT * FROM "todo" WHERE "id" = $1), "b" AS (SELECT "id" FROM "a" WHERE "title" = $2) SELECT * FROM "b" "#
+	);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_cte_ok_recursive_tree() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	// Note: The todo `project_id` is used as the parent todo id for this test.
+	let db_pool = init_db().await?;
+	let mut parent_id: Option<i64> = None;
+	for title in ["root", "child", "grandchild"] {
+		let fields = vec![("title", title).into(), ("project_id", parent_id).into()];
+		let (id,): (i64,) = sqlb::insert()
+			.table("todo")
+			.data(fields)
+			.returning(&["id"])
+			.fetch_one(&db_pool)
+			.await?;
+		parent_id = Some(id);
+	}
+	sqlb::insert()
+		.table("todo")
+		.data(vec![("title", "other").into()])
+		.exec(&db_pool)
+		.await?;
+
+	// -- Exec
+	let sb = sqlb::select()
+		.with_recursive(
+			"tree",
+			sqlb::select()
+				.table("todo")
+				.columns(&["id", "title"])
+				.and_where_eq("title", "root")
+				.union_all(
+					sqlb::select()
+						.table_as("todo", "t")
+						.columns(&["t.id", "t.title"])
+						.inner_join(sqlb::join("tree").alias("p").on_cols_eq("t.project_id", "p.id")),
+				),
+		)
+		.table("tree")
+		.columns(&["title"])
+		.order_by("id");
+	let titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"WITH RECURSIVE "tree" AS (SELECT "id", "title" FROM "todo" WHERE "title" = $1 UNION ALL SELECT "t"."id", "t"."title" FROM "todo" AS "t" INNER JOIN "tree" AS "p" ON "t"."project_id" = "p"."id") SELECT "title" FROM "tree" ORDER BY "id" "#
+	);
+	let titles: Vec<&str> = titles.iter().map(|t| t.0.as_str()).collect();
+	assert_eq!(titles, vec!["root", "child", "grandchild"]);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_cte_ok_select_with() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_prefix = "sb_cte_ok_select_with";
+	util_insert_many_todos(&db_pool, fx_prefix, 4).await?;
+
+	// -- Exec
+	let sb = sqlb::select()
+		.with(
+			"matching",
+			sqlb::select()
+				.table("todo")
+				.columns(&["title"])
+				.and_where("title", "LIKE", format!("{fx_prefix}%")),
+		)
+		.table("matching")
+		.and_where("title", ">", format!("{fx_prefix}-01"))
+		.order_by("title");
+	let titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(titles, vec![(format!("{fx_prefix}-02"),), (format!("{fx_prefix}-03"),)]);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_cte_ok_data_modifying() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_prefix = "sb_cte_ok_data_modifying";
+	util_insert_many_todos(&db_pool, fx_prefix, 3).await?;
+
+	// -- Exec
+	// Note: `WITH "moved" AS (DELETE ... RETURNING ...) INSERT ...` with the CTE values numbered first.
+	let sb = sqlb::insert()
+		.with(
+			"moved",
+			sqlb::delete()
+				.table("todo")
+				.and_where_eq("title", format!("{fx_prefix}-01"))
+				.returning(&["id", "title"]),
+		)
+		.table("todo")
+		.data(vec![("title", format!("{fx_prefix}-new")).into()])
+		.returning(&["title"]);
+	let (title,): (String,) = sb.fetch_one(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(title, format!("{fx_prefix}-new"));
+	let titles: Vec<(String,)> = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where("title", "LIKE", format!("{fx_prefix}%"))
+		.order_by("title")
+		.fetch_all(&db_pool)
+		.await?;
+	assert_eq!(
+		titles,
+		vec![
+			(format!("{fx_prefix}-00"),),
+			(format!("{fx_prefix}-02"),),
+			(format!("{fx_prefix}-new"),)
+		]
+	);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_cte_ok_delete_returning_select() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_prefix = "sb_cte_ok_delete_returning_select";
+	util_insert_many_todos(&db_pool, fx_prefix, 3).await?;
+
+	// -- Exec
+	let sb = sqlb::select()
+		.with(
+			"deleted",
+			sqlb::delete()
+				.table("todo")
+				.and_where("title", "LIKE", format!("{fx_prefix}%"))
+				.returning(&["title"]),
+		)
+		.table("deleted")
+		.columns(&["title"])
+		.order_by("title");
+	let titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(titles.len(), 3);
+	let count: Vec<(i64,)> = sqlb::select()
+		.table("todo")
+		.columns(&["id"])
+		.and_where("title", "LIKE", format!("{fx_prefix}%"))
+		.fetch_all(&db_pool)
+		.await?;
+	assert!(count.is_empty(), "todos should be deleted, but was: {:?}", count);
+
+	Ok(())
+}
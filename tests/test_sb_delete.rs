@@ -1,6 +1,6 @@
 mod utils;
 
-use sqlb::{HasFields, SqlBuilder};
+use sqlb::{Cond, HasFields, SqlBuilder};
 use std::error::Error;
 use utils::{init_db, util_fetch_all_todos, util_insert_todo};
 
@@ -16,6 +16,45 @@ fn sb_delete_err_all() {
 	// should panic
 }
 
+#[serial]
+#[test]
+fn sb_delete_err_all_try_sql() {
+	let sb = sqlb::delete().table("todo");
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::UnguardedDelete { .. })),
+		"should be UnguardedDelete error, but was: {:?}",
+		res
+	);
+}
+
+#[serial]
+#[test]
+fn sb_delete_err_missing_table() {
+	let sb = sqlb::delete_all();
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::MissingTable)),
+		"should be MissingTable error, but was: {:?}",
+		res
+	);
+}
+
+#[serial]
+#[test]
+fn sb_delete_err_invalid_identifier() {
+	let sb = sqlb::delete().table(r#"todo" WHERE 1=1; --"#).and_where_eq("id", 123);
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::InvalidIdentifier { .. })),
+		"should be InvalidIdentifier error, but was: {:?}",
+		res
+	);
+}
+
 #[serial]
 #[test]
 fn sb_delete_ok_all() {
@@ -107,3 +146,43 @@ async fn sb_delete_ok_return_many() -> Result<(), Box<dyn Error>> {
 
 	Ok(())
 }
+
+#[serial]
+#[tokio::test]
+async fn sb_delete_ok_using() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let mut project_ids = Vec::new();
+	for name in ["archived", "active"] {
+		let sb = sqlb::insert()
+			.table("project")
+			.data(vec![("name", name).into()])
+			.returning(&["id"]);
+		let (id,): (i64,) = sb.fetch_one(&db_pool).await?;
+		project_ids.push(id);
+	}
+	for (title, project_id) in [("title 01", project_ids[0]), ("title 02", project_ids[1])] {
+		let fields = vec![("title", title).into(), ("project_id", project_id).into()];
+		sqlb::insert().table("todo").data(fields).exec(&db_pool).await?;
+	}
+
+	// -- Exec
+	let sb = sqlb::delete()
+		.table("todo")
+		.using_as("project", "p")
+		.and_where_cond(Cond::cols_eq("todo.project_id", "p.id"))
+		.and_where_eq("p.name", "archived");
+	let count = sb.exec(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"DELETE FROM "todo" USING "project" AS "p" WHERE "todo"."project_id" = "p"."id" AND "p"."name" = $1 "#
+	);
+	assert_eq!(count, 1);
+	let todos = util_fetch_all_todos(&db_pool).await?;
+	let titles: Vec<&str> = todos.iter().map(|t| t.title.as_str()).collect();
+	assert_eq!(titles, vec!["title 02"]);
+
+	Ok(())
+}
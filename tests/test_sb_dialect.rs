@@ -0,0 +1,176 @@
+use sqlb::{Dialect, SqlBuilder};
+
+#[test]
+fn sb_dialect_ok_select_sql() {
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["id", "title"])
+		.and_where_eq("title", "title 01")
+		.and_where("id", ">", 2)
+		.order_by("!id")
+		.limit(10)
+		.offset(5);
+
+	assert_eq!(
+		sb.sql_for(Dialect::Postgres),
+		r#"SELECT "id", "title" FROM "todo" WHERE "title" = $1 AND "id" > $2 ORDER BY "id" DESC LIMIT 10 OFFSET 5 "#
+	);
+	assert_eq!(
+		sb.sql_for(Dialect::Sqlite),
+		r#"SELECT "id", "title" FROM "todo" WHERE "title" = ? AND "id" > ? ORDER BY "id" DESC LIMIT 10 OFFSET 5 "#
+	);
+	assert_eq!(
+		sb.sql_for(Dialect::MySql),
+		"SELECT `id`, `title` FROM `todo` WHERE `title` = ? AND `id` > ? ORDER BY `id` DESC LIMIT 10 OFFSET 5 "
+	);
+}
+
+#[test]
+fn sb_dialect_ok_insert_update_sql() {
+	let fields = vec![("title", "title 01").into(), ("description", "desc 01").into()];
+	let sb = sqlb::insert().table("todo").data(fields).returning(&["id"]);
+	assert_eq!(
+		sb.sql_for(Dialect::Sqlite),
+		r#"INSERT INTO "todo" ("title", "description") VALUES (?, ?) RETURNING "id" "#
+	);
+
+	let fields = vec![("title", "title 02").into()];
+	let sb = sqlb::update().table("todo").data(fields).and_where_eq("id", 1);
+	assert_eq!(
+		sb.sql_for(Dialect::MySql),
+		"UPDATE `todo` SET `title` = ? WHERE `id` = ? "
+	);
+
+	// -- Insert select with on conflict (SQLite requires a WHERE)
+	let sb = sqlb::insert()
+		.table("todo")
+		.data_select(
+			&["title"],
+			sqlb::select()
+				.table("todo_src")
+				.columns(&["title"])
+				.and_where_cond(sqlb::Cond::all(vec![])),
+		)
+		.on_conflict(&["title"])
+		.do_nothing();
+	assert_eq!(
+		sb.sql_for(Dialect::Sqlite),
+		r#"INSERT INTO "todo" ("title") SELECT "title" FROM "todo_src" WHERE TRUE ON CONFLICT ("title") DO NOTHING "#
+	);
+
+	// -- Empty data (MySQL does not support DEFAULT VALUES)
+	let sb = sqlb::insert().table("todo").data(vec![]);
+	assert_eq!(sb.sql_for(Dialect::Sqlite), r#"INSERT INTO "todo" DEFAULT VALUES "#);
+	assert_eq!(sb.sql_for(Dialect::MySql), "INSERT INTO `todo` () VALUES () ");
+}
+
+#[test]
+fn sb_dialect_err_unsupported() {
+	// -- MySQL does not support RETURNING
+	let sb = sqlb::delete().table("todo").and_where_eq("id", 1).returning(&["id"]);
+	let res = sb.try_sql_for(Dialect::MySql);
+	assert!(
+		matches!(
+			res,
+			Err(sqlb::Error::DialectUnsupported {
+				dialect: Dialect::MySql,
+				..
+			})
+		),
+		"should be DialectUnsupported error, but was: {:?}",
+		res
+	);
+
+	// -- The IN list is bound as a PostgreSQL array
+	let sb = sqlb::select().table("todo").and_where_in("id", vec![1i64, 2]);
+	let res = sb.try_sql_for(Dialect::Sqlite);
+	assert!(
+		matches!(res, Err(sqlb::Error::DialectUnsupported { .. })),
+		"should be DialectUnsupported error, but was: {:?}",
+		res
+	);
+
+	// -- MySQL has no `UPDATE ... FROM`, and only PostgreSQL has `DELETE ... USING`
+	let sb = sqlb::update()
+		.table("todo")
+		.data_cols(&[("title", "p.name")])
+		.from_as("project", "p")
+		.and_where_cond(sqlb::Cond::cols_eq("todo.project_id", "p.id"));
+	let res = sb.try_sql_for(Dialect::MySql);
+	assert!(
+		matches!(res, Err(sqlb::Error::DialectUnsupported { .. })),
+		"should be DialectUnsupported error, but was: {:?}",
+		res
+	);
+	let sb = sqlb::delete()
+		.table("todo")
+		.using("project")
+		.and_where_cond(sqlb::Cond::cols_eq("todo.project_id", "project.id"));
+	let res = sb.try_sql_for(Dialect::Sqlite);
+	assert!(
+		matches!(res, Err(sqlb::Error::DialectUnsupported { .. })),
+		"should be DialectUnsupported error, but was: {:?}",
+		res
+	);
+
+	// -- SQLite parses the `ON CONFLICT` after a select without WHERE as a join constraint
+	let sb = sqlb::insert()
+		.table("todo")
+		.data_select(&["title"], sqlb::select().table("todo_src").columns(&["title"]))
+		.on_conflict(&["title"])
+		.do_nothing();
+	let res = sb.try_sql_for(Dialect::Sqlite);
+	assert!(
+		matches!(
+			res,
+			Err(sqlb::Error::DialectUnsupported {
+				dialect: Dialect::Sqlite,
+				..
+			})
+		),
+		"should be DialectUnsupported error, but was: {:?}",
+		res
+	);
+
+	// -- SQLite has no `DEFAULT` value
+	let sb = sqlb::insert()
+		.table("todo")
+		.data(vec![("title", "title 01").into(), ("ctime", sqlb::SqlDefault).into()]);
+	let res = sb.try_sql_for(Dialect::Sqlite);
+	assert!(
+		matches!(
+			res,
+			Err(sqlb::Error::DialectUnsupported {
+				dialect: Dialect::Sqlite,
+				..
+			})
+		),
+		"should be DialectUnsupported error, but was: {:?}",
+		res
+	);
+
+	// -- SQLite has no row locking, and MySQL no `FOR KEY SHARE`
+	let sb = sqlb::select().table("todo").for_update();
+	let res = sb.try_sql_for(Dialect::Sqlite);
+	assert!(
+		matches!(res, Err(sqlb::Error::DialectUnsupported { .. })),
+		"should be DialectUnsupported error, but was: {:?}",
+		res
+	);
+	let sb = sqlb::select().table("todo").for_key_share();
+	let res = sb.try_sql_for(Dialect::MySql);
+	assert!(
+		matches!(res, Err(sqlb::Error::DialectUnsupported { .. })),
+		"should be DialectUnsupported error, but was: {:?}",
+		res
+	);
+	assert_eq!(
+		sqlb::select()
+			.table("todo")
+			.limit(1)
+			.for_update()
+			.skip_locked()
+			.sql_for(Dialect::MySql),
+		"SELECT * FROM `todo` LIMIT 1 FOR UPDATE SKIP LOCKED "
+	);
+}
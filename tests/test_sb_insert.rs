@@ -2,9 +2,9 @@ mod utils;
 
 use crate::utils::Todo;
 use serial_test::serial;
-use sqlb::{Field, HasFields, Raw};
+use sqlb::{Expr, Field, HasFields, Raw, SqlBuilder};
 use std::error::Error;
-use utils::{init_db, util_fetch_all_todos, TodoPatch};
+use utils::{init_db, util_fetch_all_todos, util_insert_many_todos, TodoPatch};
 
 #[serial]
 #[tokio::test]
@@ -111,3 +111,301 @@ async fn sb_insert_ok_raw() -> Result<(), Box<dyn Error>> {
 
 	Ok(())
 }
+
+#[serial]
+#[tokio::test]
+async fn sb_insert_ok_expr() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+
+	// -- Exec
+	let rows: Vec<Vec<Field>> = vec![
+		vec![
+			("title", Expr::new("upper({}) || {}").bind("title").bind(" 01")).into(),
+			("ctime", Raw("now()")).into(),
+			("description", "desc 01").into(),
+		],
+		vec![
+			("title", Expr::new("upper({}) || {}").bind("title").bind(" 02")).into(),
+			("ctime", Raw("now()")).into(),
+			("description", "desc 02").into(),
+		],
+	];
+	let sb = sqlb::insert()
+		.table("todo")
+		.data_rows(rows)
+		.returning(&["title", "description"]);
+	let rows: Vec<(String, String)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"INSERT INTO "todo" ("title", "ctime", "description") VALUES (upper($1) || $2, now(), $3), (upper($4) || $5, now(), $6) RETURNING "title", "description" "#
+	);
+	assert_eq!(
+		rows,
+		vec![
+			("TITLE 01".to_string(), "desc 01".to_string()),
+			("TITLE 02".to_string(), "desc 02".to_string())
+		]
+	);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_insert_ok_default_values() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+
+	// -- Exec
+	let sb = sqlb::insert().table("todo").data(vec![]).returning(&["id"]);
+	let (id_1,): (i64,) = sb.fetch_one(&db_pool).await?;
+	let fields: Vec<Field> = vec![("id", sqlb::SqlDefault).into(), ("title", "title 02").into()];
+	let sb_field = sqlb::insert().table("todo").data(fields).returning(&["id"]);
+	let (id_2,): (i64,) = sb_field.fetch_one(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(sb.sql(), r#"INSERT INTO "todo" DEFAULT VALUES RETURNING "id" "#);
+	assert_eq!(
+		sb_field.sql(),
+		r#"INSERT INTO "todo" ("id", "title") VALUES (DEFAULT, $1) RETURNING "id" "#
+	);
+	assert_eq!(id_2, id_1 + 1);
+	assert_eq!(sqlb::select().table("todo").fetch_count(&db_pool).await?, 2);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_insert_ok_data_rows() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_rows: Vec<Vec<Field>> = vec![
+		vec![("title", "title 01").into(), ("ctime", Raw("now()")).into()],
+		vec![("title", "title 02").into(), ("ctime", Raw("now()")).into()],
+		vec![("title", "title 03").into(), ("ctime", Raw("now()")).into()],
+	];
+
+	// -- Exec
+	let sb = sqlb::insert().table("todo").data_rows(fx_rows).returning(&["title"]);
+	let titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"INSERT INTO "todo" ("title", "ctime") VALUES ($1, now()), ($2, now()), ($3, now()) RETURNING "title" "#
+	);
+	assert_eq!(titles.len(), 3);
+	assert_eq!(titles[2].0, "title 03");
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_insert_ok_data_rows_from() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_patches = vec![
+		TodoPatch {
+			title: Some("title 01".to_string()),
+			desc: None,
+		},
+		TodoPatch {
+			title: Some("title 02".to_string()),
+			desc: Some("desc 02".to_string()),
+		},
+	];
+
+	// -- Exec
+	let row_affected = sqlb::insert().table("todo").data_rows_from(fx_patches).exec(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(row_affected, 2);
+	let todos = util_fetch_all_todos(&db_pool).await?;
+	assert_eq!(todos[0].desc.as_deref(), Some("desc 02"));
+	assert_eq!(todos[1].desc, None);
+
+	Ok(())
+}
+
+#[test]
+fn sb_insert_err_data_rows_mismatch() {
+	let fx_rows: Vec<Vec<Field>> = vec![
+		vec![("title", "title 01").into(), ("description", "desc 01").into()],
+		vec![("description", "desc 02").into(), ("title", "title 02").into()],
+	];
+
+	let res = sqlb::insert().table("todo").data_rows(fx_rows).try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::InsertRowMismatch { row_idx: 1 })),
+		"should be InsertRowMismatch error, but was: {:?}",
+		res
+	);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_insert_ok_exec_chunked() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	// Note: 2 bound values per row, so 80_000 values, above the 65535 limit.
+	let fx_rows: Vec<Vec<Field>> = (0..40_000)
+		.map(|idx| vec![("title", format!("title {idx}")).into(), ("project_id", idx as i64).into()])
+		.collect();
+	let sb = sqlb::insert().table("todo").data_rows(fx_rows);
+
+	// -- Exec & Check
+	let res = sb.exec(&db_pool).await;
+	assert!(
+		matches!(res, Err(sqlb::Error::TooManyBindParams { count: 80_000, .. })),
+		"should be TooManyBindParams error, but was: {:?}",
+		res
+	);
+
+	let row_affected = sb.exec_chunked(&db_pool).await?;
+	assert_eq!(row_affected, 40_000);
+	let (count,): (i64,) = sqlb::select().table("todo").columns(&["count(*)"]).fetch_one(&db_pool).await?;
+	assert_eq!(count, 40_000);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_insert_ok_on_conflict_do_update() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let (fx_id,): (i64,) = sqlb::insert()
+		.table("project")
+		.data(vec![("name", "project A").into()])
+		.returning(&["id"])
+		.fetch_one(&db_pool)
+		.await?;
+
+	// -- Exec
+	let sb = sqlb::insert()
+		.table("project")
+		.data(vec![("id", fx_id).into(), ("name", "project A").into()])
+		.on_conflict(&["name"])
+		.do_update_excluded(&["name"])
+		.do_update(vec![("id", fx_id + 100).into()])
+		.do_update_and_where("project.id", "=", fx_id)
+		.returning(&["id", "name"]);
+	let (id, name): (i64, String) = sb.fetch_one(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"INSERT INTO "project" ("id", "name") VALUES ($1, $2) ON CONFLICT ("name") DO UPDATE SET "name" = EXCLUDED."name", "id" = $3 WHERE "project"."id" = $4 RETURNING "id", "name" "#
+	);
+	assert_eq!(id, fx_id + 100);
+	assert_eq!(name, "project A");
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_insert_ok_on_conflict_do_nothing() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	sqlb::insert()
+		.table("project")
+		.data(vec![("name", "project A").into()])
+		.exec(&db_pool)
+		.await?;
+
+	// -- Exec
+	let sb = sqlb::insert()
+		.table("project")
+		.data(vec![("name", "project A").into()])
+		.on_conflict_constraint("project_name_key")
+		.do_nothing()
+		.returning(&["id"]);
+	let res: Option<(i64,)> = sb.fetch_optional(&db_pool).await?;
+
+	// -- Check
+	assert!(res.is_none(), "should not have inserted/returned a row");
+
+	Ok(())
+}
+
+#[test]
+fn sb_insert_err_on_conflict_without_action() {
+	let res = sqlb::insert()
+		.table("project")
+		.data(vec![("name", "project A").into()])
+		.on_conflict(&["name"])
+		.try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::OnConflictWithoutAction)),
+		"should be OnConflictWithoutAction error, but was: {:?}",
+		res
+	);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_insert_ok_data_select() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_title_prefix = "sb_insert_ok_data_select";
+	util_insert_many_todos(&db_pool, fx_title_prefix, 3).await?;
+	util_insert_many_todos(&db_pool, "other", 2).await?;
+	sqlb::insert()
+		.table("project")
+		.data(vec![("name", format!("{fx_title_prefix}-01")).into()])
+		.exec(&db_pool)
+		.await?;
+
+	// -- Exec
+	let sb = sqlb::insert()
+		.table("project")
+		.data_select(
+			&["name"],
+			sqlb::select()
+				.table("todo")
+				.columns(&["title"])
+				.and_where("title", "LIKE", format!("{fx_title_prefix}%")),
+		)
+		.on_conflict(&["name"])
+		.do_nothing()
+		.returning(&["name"]);
+	let mut names: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"INSERT INTO "project" ("name") SELECT "title" FROM "todo" WHERE "title" LIKE $1 ON CONFLICT ("name") DO NOTHING RETURNING "name" "#
+	);
+	names.sort();
+	assert_eq!(
+		names,
+		vec![(format!("{fx_title_prefix}-00"),), (format!("{fx_title_prefix}-02"),)]
+	);
+
+	Ok(())
+}
+
+#[test]
+fn sb_insert_err_data_select_mismatch() {
+	let res = sqlb::insert()
+		.table("project")
+		.data_select(&["name"], sqlb::select().table("todo").columns(&["id", "title"]))
+		.try_sql();
+
+	assert!(
+		matches!(
+			res,
+			Err(sqlb::Error::InsertSelectColumnMismatch { names: 1, columns: 2 })
+		),
+		"should be InsertSelectColumnMismatch error, but was: {:?}",
+		res
+	);
+}
@@ -0,0 +1,141 @@
+mod utils;
+
+use crate::utils::{util_insert_many_todos, Todo};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serial_test::serial;
+use sqlb::SqlBuilder;
+use std::error::Error;
+use utils::init_db;
+
+#[serial]
+#[tokio::test]
+async fn sb_keyset_ok_pages() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_title_prefix = "sb_keyset_ok_pages";
+	util_insert_many_todos(&db_pool, fx_title_prefix, 5).await?;
+	let page_sb = || {
+		sqlb::select()
+			.table("todo")
+			.and_where("title", "LIKE", format!("{fx_title_prefix}%"))
+			.order_bys(&["title", "id"])
+			.limit(2)
+	};
+
+	// -- Exec
+	let mut pages: Vec<Vec<String>> = Vec::new();
+	let mut cursor: Option<String> = None;
+	loop {
+		let sb = match &cursor {
+			Some(cursor) => page_sb().after_cursor(cursor),
+			None => page_sb(),
+		};
+		let page = sb.fetch_keyset::<_, Todo>(&db_pool).await?;
+		pages.push(page.items.into_iter().map(|t| t.title).collect());
+		cursor = page.next_cursor;
+		if cursor.is_none() {
+			break;
+		}
+	}
+
+	// -- Check
+	assert_eq!(
+		page_sb().after_cursor(&cursor_of(&db_pool).await?).sql(),
+		r#"SELECT * FROM "todo" WHERE "title" LIKE $1 AND ("title", "id") > ($2, $3) ORDER BY "title", "id" LIMIT 2 "#
+	);
+	assert_eq!(
+		pages,
+		vec![
+			vec![format!("{fx_title_prefix}-00"), format!("{fx_title_prefix}-01")],
+			vec![format!("{fx_title_prefix}-02"), format!("{fx_title_prefix}-03")],
+			vec![format!("{fx_title_prefix}-04")],
+		]
+	);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_keyset_ok_mixed_directions() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_data: &[(&str, i64)] = &[("title 01", 1), ("title 02", 1), ("title 03", 2), ("title 04", 2)];
+	for (title, project_id) in fx_data {
+		let fields = vec![("title", *title).into(), ("project_id", *project_id).into()];
+		sqlb::insert().table("todo").data(fields).exec(&db_pool).await?;
+	}
+	let page_sb = || {
+		sqlb::select()
+			.table("todo")
+			.columns(&["id", "project_id", "title"])
+			.order_bys(&["!project_id", "title"])
+			.limit(3)
+	};
+
+	// -- Exec
+	let page_1 = page_sb().fetch_keyset::<_, (i64, i64, String)>(&db_pool).await?;
+	let cursor = page_1.next_cursor.ok_or("should have a next cursor")?;
+	let sb = page_sb().after_cursor(&cursor);
+	let page_2 = sb.fetch_keyset::<_, (i64, i64, String)>(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT "id", "project_id", "title" FROM "todo" WHERE ("project_id" < $1 OR ("project_id" = $2 AND "title" > $3)) ORDER BY "project_id" DESC, "title" LIMIT 3 "#
+	);
+	assert_eq!(sb.vals().count(), 3);
+	let titles_1: Vec<&str> = page_1.items.iter().map(|r| r.2.as_str()).collect();
+	assert_eq!(titles_1, vec!["title 03", "title 04", "title 01"]);
+	let titles_2: Vec<&str> = page_2.items.iter().map(|r| r.2.as_str()).collect();
+	assert_eq!(titles_2, vec!["title 02"]);
+	assert!(page_2.next_cursor.is_none(), "should be the last page");
+
+	Ok(())
+}
+
+#[test]
+fn sb_keyset_err_invalid() {
+	// -- Not a cursor
+	let sb = sqlb::select().table("todo").order_by("id").after_cursor("not a cursor");
+	let res = sb.try_sql();
+	assert!(
+		matches!(res, Err(sqlb::Error::InvalidCursor)),
+		"should be InvalidCursor error, but was: {:?}",
+		res
+	);
+
+	// -- Malformed cursors (e.g., multibyte tag, bad length, bad payload)
+	for buf in ["é1:x", "i", "i1", "i9:1", "ix:1", "i1:x", "u3:abc", "i1:1s"] {
+		let cursor = URL_SAFE_NO_PAD.encode(buf);
+		let sb = sqlb::select().table("todo").order_by("id").after_cursor(&cursor);
+		let res = sb.try_sql();
+		assert!(
+			matches!(res, Err(sqlb::Error::InvalidCursor)),
+			"should be InvalidCursor error for {:?}, but was: {:?}",
+			buf,
+			res
+		);
+	}
+
+	// -- No order by
+	let sb = sqlb::select().table("todo").after_cursor("aTE6MQ");
+	let res = sb.try_sql();
+	assert!(
+		matches!(res, Err(sqlb::Error::KeysetInvalidOrder)),
+		"should be KeysetInvalidOrder error, but was: {:?}",
+		res
+	);
+}
+
+/// A cursor for the first todo, ordered by title and id.
+async fn cursor_of(db_pool: &sqlx::Pool<sqlx::Postgres>) -> Result<String, Box<dyn Error>> {
+	let page = sqlb::select()
+		.table("todo")
+		.order_bys(&["title", "id"])
+		.limit(1)
+		.fetch_keyset::<_, Todo>(db_pool)
+		.await?;
+	Ok(page.next_cursor.ok_or("should have a next cursor")?)
+}
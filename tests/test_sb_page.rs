@@ -0,0 +1,99 @@
+mod utils;
+
+use crate::utils::{util_insert_many_todos, Todo};
+use serial_test::serial;
+use std::error::Error;
+use utils::init_db;
+
+#[serial]
+#[tokio::test]
+async fn sb_page_ok_fetch_page() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_title_prefix = "sb_page_ok_fetch_page";
+	util_insert_many_todos(&db_pool, fx_title_prefix, 5).await?;
+	util_insert_many_todos(&db_pool, "other", 2).await?;
+
+	// -- Exec
+	// Note: The builder limit is ignored, and the order by value is not bound for the count query.
+	let sb = sqlb::select()
+		.table("todo")
+		.and_where("title", "LIKE", format!("{fx_title_prefix}%"))
+		.order_by("title")
+		.add_order_by(sqlb::order_expr(sqlb::expr("length({})").bind("x")))
+		.limit(1);
+	let page_2 = sb.fetch_page::<_, Todo>(&db_pool, 2, 2).await?;
+	let page_3 = sb.fetch_page::<_, Todo>(&db_pool, 3, 2).await?;
+
+	// -- Check
+	assert_eq!(page_2.total, 5);
+	assert_eq!((page_2.page, page_2.page_size, page_2.page_count()), (2, 2, 3));
+	let titles: Vec<String> = page_2.items.into_iter().map(|t| t.title).collect();
+	assert_eq!(
+		titles,
+		vec![format!("{fx_title_prefix}-02"), format!("{fx_title_prefix}-03")]
+	);
+	assert_eq!(page_3.items.len(), 1);
+	assert_eq!(page_3.total, 5);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_page_ok_fetch_page_over() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_title_prefix = "sb_page_ok_fetch_page_over";
+	util_insert_many_todos(&db_pool, fx_title_prefix, 5).await?;
+
+	// -- Exec
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["id", "title"])
+		.and_where("title", "LIKE", format!("{fx_title_prefix}%"))
+		.order_by("!title");
+	let mut tx = db_pool.begin().await?;
+	let page_1 = sb.fetch_page_over::<_, (i64, String)>(&mut *tx, 1, 2).await?;
+	// Note: Beyond the last page, the total comes from the count query.
+	let page_9 = sb.fetch_page_over::<_, (i64, String)>(&mut *tx, 9, 2).await?;
+	tx.commit().await?;
+
+	// -- Check
+	assert_eq!(page_1.total, 5);
+	let titles: Vec<String> = page_1.items.into_iter().map(|t| t.1).collect();
+	assert_eq!(
+		titles,
+		vec![format!("{fx_title_prefix}-04"), format!("{fx_title_prefix}-03")]
+	);
+	assert!(page_9.items.is_empty(), "should be empty, but was: {:?}", page_9.items);
+	assert_eq!(page_9.total, 5);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_page_err_invalid_page() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let sb = sqlb::select().table("todo").order_by("id");
+
+	// -- Exec & Check
+	for (page, page_size) in [(0, 10), (1, 0), (-1, 10), (i64::MAX, 2)] {
+		let res = sb.fetch_page::<_, Todo>(&db_pool, page, page_size).await;
+		assert!(
+			matches!(res, Err(sqlb::Error::InvalidPage { .. })),
+			"should be InvalidPage error, but was: {:?}",
+			res
+		);
+		let res = sb.fetch_page_over::<_, Todo>(&db_pool, page, page_size).await;
+		assert!(
+			matches!(res, Err(sqlb::Error::InvalidPage { .. })),
+			"should be InvalidPage error, but was: {:?}",
+			res
+		);
+	}
+
+	Ok(())
+}
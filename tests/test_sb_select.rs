@@ -1,7 +1,9 @@
 mod utils;
 
 use crate::utils::{util_insert_many_todos, Todo};
+use futures::{StreamExt, TryStreamExt};
 use serial_test::serial;
+use sqlb::{Agg, SqlBuilder};
 use std::error::Error;
 use utils::init_db;
 
@@ -44,7 +46,7 @@ async fn sb_select_ok_limit_offset() -> Result<(), Box<dyn Error>> {
 
 	// -- Check
 	assert_eq!(todos.len(), 3, "number of todos");
-	let todo_02 = todos.get(0).unwrap();
+	let todo_02 = todos.first().unwrap();
 	assert_eq!(todo_02.title, "sb_select_ok_limit_offset-02");
 
 	// -- Clean
@@ -105,3 +107,439 @@ async fn sb_select_ok_count() -> Result<(), Box<dyn Error>> {
 
 	Ok(())
 }
+
+#[serial]
+#[tokio::test]
+async fn sb_select_ok_inner_join() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_title_prefix = "sb_select_ok_inner_join";
+	let (project_a_id,): (i64,) = sqlb::insert()
+		.table("project")
+		.data(vec![("name", "project A").into()])
+		.returning(&["id"])
+		.fetch_one(&db_pool)
+		.await?;
+	let (project_b_id,): (i64,) = sqlb::insert()
+		.table("project")
+		.data(vec![("name", "project B").into()])
+		.returning(&["id"])
+		.fetch_one(&db_pool)
+		.await?;
+	for (idx, project_id) in [project_a_id, project_a_id, project_b_id].iter().enumerate() {
+		let title = format!("{fx_title_prefix}-{:0>2}", idx);
+		let fields = vec![("title", title).into(), ("project_id", *project_id).into()];
+		sqlb::insert().table("todo").data(fields).exec(&db_pool).await?;
+	}
+
+	// -- Exec
+	let sb = sqlb::select()
+		.table_as("todo", "t")
+		.columns(&["t.title", "p.name"])
+		.inner_join(
+			sqlb::join("project")
+				.alias("p")
+				.on_cols_eq("p.id", "t.project_id")
+				.on_eq("p.name", "project A"),
+		)
+		.and_where("t.title", "LIKE", format!("{fx_title_prefix}%"))
+		.order_by("t.title");
+	let rows: Vec<(String, String)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT "t"."title", "p"."name" FROM "todo" AS "t" INNER JOIN "project" AS "p" ON "p"."id" = "t"."project_id" AND "p"."name" = $1 WHERE "t"."title" LIKE $2 ORDER BY "t"."title" "#
+	);
+	assert_eq!(rows.len(), 2, "number of rows");
+	assert_eq!(rows[0].0, "sb_select_ok_inner_join-00");
+	assert_eq!(rows[0].1, "project A");
+
+	Ok(())
+}
+
+#[test]
+fn sb_select_ok_table_reset_alias() {
+	let sb = sqlb::select().table_as("todo", "t").table("project");
+
+	assert_eq!(sb.sql(), r#"SELECT * FROM "project" "#);
+}
+
+#[test]
+fn sb_select_err_join_without_on() {
+	let sb = sqlb::select().table("todo").left_join(sqlb::join("project"));
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::JoinWithoutOn { .. })),
+		"should be JoinWithoutOn error, but was: {:?}",
+		res
+	);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_select_ok_group_by_having() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_data: &[(&str, i64)] = &[("title 01", 1), ("title 02", 1), ("title 03", 1), ("title 04", 2)];
+	for (title, project_id) in fx_data {
+		let fields = vec![("title", *title).into(), ("project_id", *project_id).into()];
+		sqlb::insert().table("todo").data(fields).exec(&db_pool).await?;
+	}
+
+	// -- Exec
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["project_id"])
+		.agg(Agg::count_all().alias("count"))
+		.agg(Agg::max("title").alias("max_title"))
+		.and_where("title", "!=", "title 02")
+		.group_by(&["project_id"])
+		.having("count(*)", ">=", 2i64)
+		.order_by("project_id");
+	let rows: Vec<(i64, i64, String)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT "project_id", count(*) AS "count", max("title") AS "max_title" FROM "todo" WHERE "title" != $1 GROUP BY "project_id" HAVING count(*) >= $2 ORDER BY "project_id" "#
+	);
+	assert_eq!(rows, vec![(1, 2, "title 03".to_string())]);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_select_ok_fetch_stream() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_title_prefix = "test_sb_select_ok_fetch_stream";
+	let fx_ids = util_insert_many_todos(&db_pool, fx_title_prefix, 5).await?;
+
+	// -- Exec
+	let sb = sqlb::select().table("todo").and_where_in("id", fx_ids.clone()).order_by("id");
+	let mut stream = sb.fetch_stream::<_, Todo>(&db_pool);
+	let mut ids = Vec::new();
+	while let Some(todo) = stream.try_next().await? {
+		ids.push(todo.id);
+	}
+
+	// -- Check
+	assert_eq!(ids, fx_ids);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_select_err_fetch_stream_invalid() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+
+	// -- Exec
+	// Note: Uses the SqlBuilder trait version, and the sql error comes as the first stream item.
+	let sb = sqlb::select().table("todo").columns(&["bad\"name"]);
+	let res: Vec<sqlb::Result<Todo>> = SqlBuilder::fetch_stream(&sb, &db_pool).collect().await;
+
+	// -- Check
+	assert_eq!(res.len(), 1);
+	assert!(
+		matches!(res[0], Err(sqlb::Error::InvalidIdentifier { .. })),
+		"should be InvalidIdentifier error, but was: {:?}",
+		res
+	);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_select_ok_for_update_skip_locked() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_title_prefix = "sb_select_ok_for_update_skip_locked";
+	let fx_ids = util_insert_many_todos(&db_pool, fx_title_prefix, 3).await?;
+	let job_sb = || {
+		sqlb::select()
+			.table("todo")
+			.and_where_in("id", fx_ids.clone())
+			.order_by("id")
+			.limit(1)
+			.for_update()
+			.skip_locked()
+	};
+
+	// -- Exec
+	// Note: The first worker locks the first todo, so the second worker gets the next one.
+	let mut tx_1 = db_pool.begin().await?;
+	let todo_1: Todo = job_sb().fetch_one(&mut *tx_1).await?;
+	let mut tx_2 = db_pool.begin().await?;
+	let todo_2: Todo = job_sb().fetch_one(&mut *tx_2).await?;
+	tx_2.rollback().await?;
+	tx_1.rollback().await?;
+
+	// -- Check
+	assert_eq!(
+		job_sb().sql(),
+		r#"SELECT * FROM "todo" WHERE "id" = ANY($1) ORDER BY "id" LIMIT 1 FOR UPDATE SKIP LOCKED "#
+	);
+	assert_eq!(todo_1.id, fx_ids[0]);
+	assert_eq!(todo_2.id, fx_ids[1]);
+
+	Ok(())
+}
+
+#[test]
+fn sb_select_ok_lock_sql() {
+	let sb = sqlb::select()
+		.table_as("todo", "t")
+		.inner_join(sqlb::join("project").alias("p").on_cols_eq("p.id", "t.project_id"))
+		.for_no_key_update()
+		.lock_of(&["t"])
+		.nowait();
+
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT * FROM "todo" AS "t" INNER JOIN "project" AS "p" ON "p"."id" = "t"."project_id" FOR NO KEY UPDATE OF "t" NOWAIT "#
+	);
+	assert_eq!(
+		sqlb::select().table("todo").for_key_share().sql(),
+		r#"SELECT * FROM "todo" FOR KEY SHARE "#
+	);
+}
+
+#[test]
+fn sb_select_err_lock_without_strength() {
+	let sb = sqlb::select().table("todo").skip_locked();
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::LockWithoutStrength)),
+		"should be LockWithoutStrength error, but was: {:?}",
+		res
+	);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_select_ok_distinct_on() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_data: &[(&str, i64)] = &[("title 01", 1), ("title 02", 1), ("title 03", 2), ("title 04", 2)];
+	for (title, project_id) in fx_data {
+		let fields = vec![("title", *title).into(), ("project_id", *project_id).into()];
+		sqlb::insert().table("todo").data(fields).exec(&db_pool).await?;
+	}
+
+	// -- Exec
+	// Note: Latest todo per project.
+	let sb = sqlb::select()
+		.table("todo")
+		.distinct_on(&["project_id"])
+		.columns(&["project_id", "title"])
+		.order_bys(&["project_id", "!id"]);
+	let rows: Vec<(i64, String)> = sb.fetch_all(&db_pool).await?;
+	let project_ids: Vec<(i64,)> = sqlb::select()
+		.table("todo")
+		.distinct()
+		.columns(&["project_id"])
+		.order_by("project_id")
+		.fetch_all(&db_pool)
+		.await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT DISTINCT ON ("project_id") "project_id", "title" FROM "todo" ORDER BY "project_id", "id" DESC "#
+	);
+	assert_eq!(rows, vec![(1, "title 02".to_string()), (2, "title 04".to_string())]);
+	assert_eq!(project_ids, vec![(1,), (2,)]);
+
+	Ok(())
+}
+
+#[test]
+fn sb_select_ok_distinct_on_qualified_sql() {
+	let sb = sqlb::select()
+		.table_as("todo", "t")
+		.distinct_on(&["t.project_id"])
+		.columns(&["t.project_id", "t.title"])
+		.order_bys(&["project_id", "!t.id"]);
+
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT DISTINCT ON ("t"."project_id") "t"."project_id", "t"."title" FROM "todo" AS "t" ORDER BY "project_id", "t"."id" DESC "#
+	);
+
+	// a different qualifier is still a mismatch
+	let res = sqlb::select()
+		.table("todo")
+		.distinct_on(&["t.project_id"])
+		.order_bys(&["p.project_id"])
+		.try_sql();
+
+	assert!(
+		matches!(&res, Err(sqlb::Error::DistinctOnOrderMismatch { name }) if name == "t.project_id"),
+		"should be DistinctOnOrderMismatch error, but was: {:?}",
+		res
+	);
+}
+
+#[test]
+fn sb_select_err_distinct_on_order_mismatch() {
+	let sb = sqlb::select()
+		.table("todo")
+		.distinct_on(&["project_id"])
+		.order_bys(&["!id", "project_id"]);
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(&res, Err(sqlb::Error::DistinctOnOrderMismatch { name }) if name == "project_id"),
+		"should be DistinctOnOrderMismatch error, but was: {:?}",
+		res
+	);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_select_ok_order_by_expr_nulls() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_data: &[(&str, Option<&str>)] = &[("title 01", Some("b")), ("title 02", None), ("title 03", Some("a"))];
+	let mut fx_ids = Vec::new();
+	for (title, desc) in fx_data {
+		let fields = vec![("title", *title).into(), ("description", *desc).into()];
+		let (id,): (i64,) = sqlb::insert()
+			.table("todo")
+			.data(fields)
+			.returning(&["id"])
+			.fetch_one(&db_pool)
+			.await?;
+		fx_ids.push(id);
+	}
+
+	// -- Exec
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where("id", ">=", fx_ids[0])
+		.order_by("!description")
+		.add_order_by(sqlb::order("title").collate("C"));
+	let rows_desc_nulls: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+	let sb_nulls_last = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.add_order_by(sqlb::order("description").desc().nulls_last());
+	let rows_nulls_last: Vec<(String,)> = sb_nulls_last.fetch_all(&db_pool).await?;
+	// Note: Closest to the last todo first.
+	let sb_expr = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where("id", ">=", fx_ids[0])
+		.add_order_by(sqlb::order_expr(sqlb::expr(r#"abs("id" - {})"#).bind(fx_ids[2])))
+		.limit(2);
+	let rows_expr: Vec<(String,)> = sb_expr.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT "title" FROM "todo" WHERE "id" >= $1 ORDER BY "description" DESC, "title" COLLATE "C" "#
+	);
+	// Note: NULLs are first with DESC by default in PostgreSQL.
+	assert_eq!(
+		rows_desc_nulls,
+		vec![("title 02".to_string(),), ("title 01".to_string(),), ("title 03".to_string(),)]
+	);
+	assert_eq!(
+		sb_nulls_last.sql(),
+		r#"SELECT "title" FROM "todo" ORDER BY "description" DESC NULLS LAST "#
+	);
+	assert_eq!(rows_nulls_last.last().map(|r| r.0.as_str()), Some("title 02"));
+	assert_eq!(
+		sb_expr.sql(),
+		r#"SELECT "title" FROM "todo" WHERE "id" >= $1 ORDER BY abs("id" - $2) LIMIT 2 "#
+	);
+	assert_eq!(rows_expr, vec![("title 03".to_string(),), ("title 02".to_string(),)]);
+
+	Ok(())
+}
+
+#[test]
+fn sb_select_err_order_expr_bind_mismatch() {
+	let sb = sqlb::select()
+		.table("todo")
+		.add_order_by(sqlb::order_expr(sqlb::expr(r#"abs("id" - {})"#)));
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(
+			res,
+			Err(sqlb::Error::ExprBindMismatch {
+				expected: 1,
+				actual: 0,
+				..
+			})
+		),
+		"should be ExprBindMismatch error, but was: {:?}",
+		res
+	);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_select_ok_fetch_scalars() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_title_prefix = "sb_select_ok_fetch_scalars";
+	let fx_ids = util_insert_many_todos(&db_pool, fx_title_prefix, 5).await?;
+
+	// -- Exec
+	let title: String = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where_eq("id", fx_ids[1])
+		.fetch_scalar(&db_pool)
+		.await?;
+	let title_none: Option<String> = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where_eq("id", -1)
+		.fetch_optional_scalar(&db_pool)
+		.await?;
+	let max_id = sqlx_exec_max_id(&db_pool).await?;
+	let sb_count = sqlb::select()
+		.table("todo")
+		.and_where("title", "LIKE", format!("{fx_title_prefix}%"))
+		.order_by("title")
+		.limit(3);
+	let count = sb_count.fetch_count(&db_pool).await?;
+	let exists = sqlb::select()
+		.table("todo")
+		.and_where_eq("id", fx_ids[0])
+		.fetch_exists(&db_pool)
+		.await?;
+	let not_exists = sqlb::select()
+		.table("todo")
+		.and_where_eq("id", -1)
+		.fetch_exists(&db_pool)
+		.await?;
+
+	// -- Check
+	assert_eq!(title, format!("{fx_title_prefix}-01"));
+	assert_eq!(title_none, None);
+	assert_eq!(max_id, fx_ids[4]);
+	// Note: The limit applies, as the select is wrapped.
+	assert_eq!(count, 3);
+	assert!(exists, "should exist");
+	assert!(!not_exists, "should not exist");
+
+	Ok(())
+}
+
+/// Max todo id with the generic `sqlx_exec::fetch_scalar` (any builder).
+async fn sqlx_exec_max_id(db_pool: &sqlx::Pool<sqlx::Postgres>) -> Result<i64, Box<dyn Error>> {
+	let sb = sqlb::select().table("todo").agg(Agg::max("id"));
+	Ok(sqlb::sqlx_exec::fetch_scalar::<_, i64, _>(db_pool, &sb).await?)
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "sqlite")]
+
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::{Pool, Sqlite};
+use std::error::Error;
+
+async fn init_sqlite_db() -> Result<Pool<Sqlite>, sqlx::Error> {
+	// Note: One connection, as each in-memory connection has its own database.
+	let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await?;
+	sqlx::query("CREATE TABLE todo (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT, description TEXT)")
+		.execute(&pool)
+		.await?;
+	Ok(pool)
+}
+
+#[tokio::test]
+async fn sb_sqlite_ok_crud() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_sqlite_db().await?;
+
+	// -- Exec
+	let mut ids = Vec::new();
+	for title in ["title 01", "title 02", "title 03"] {
+		let fields = vec![("title", title).into(), ("description", None::<String>).into()];
+		let sb = sqlb::insert().table("todo").data(fields).returning(&["id"]);
+		let (id,): (i64,) = sb.fetch_one(&db_pool).await?;
+		ids.push(id);
+	}
+	let fields = vec![("description", "desc 02").into()];
+	let updated = sqlb::update()
+		.table("todo")
+		.data(fields)
+		.and_where_eq("id", ids[1])
+		.exec(&db_pool)
+		.await?;
+	let deleted = sqlb::delete().table("todo").and_where_eq("id", ids[2]).exec(&db_pool).await?;
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title", "description"])
+		.and_where("id", ">", 0)
+		.order_by("!id")
+		.offset(0);
+	let todos: Vec<(String, Option<String>)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(updated, 1);
+	assert_eq!(deleted, 1);
+	assert_eq!(
+		todos,
+		vec![
+			("title 02".to_string(), Some("desc 02".to_string())),
+			("title 01".to_string(), None)
+		]
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn sb_sqlite_err_unbindable_val() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_sqlite_db().await?;
+
+	// -- Exec
+	// Note: The arrays are PostgreSQL only.
+	let fields = vec![("title", vec!["title 01".to_string()]).into()];
+	let res = sqlb::insert().table("todo").data(fields).exec(&db_pool).await;
+
+	// -- Check
+	assert!(
+		matches!(res, Err(sqlb::Error::DialectUnsupported { .. })),
+		"should be DialectUnsupported error, but was: {:?}",
+		res
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn sb_sqlite_ok_insert_select_on_conflict() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_sqlite_db().await?;
+	sqlx::query("CREATE UNIQUE INDEX todo_title_key ON todo (title)")
+		.execute(&db_pool)
+		.await?;
+	for title in ["title 01", "title 02"] {
+		sqlb::insert()
+			.table("todo")
+			.data(vec![("title", title).into()])
+			.exec(&db_pool)
+			.await?;
+	}
+
+	// -- Exec
+	// Note: The select needs a WHERE for SQLite (`WHERE TRUE` here).
+	let sb = sqlb::insert()
+		.table("todo")
+		.data_select(
+			&["title"],
+			sqlb::select()
+				.table("todo")
+				.columns(&["title"])
+				.and_where_cond(sqlb::Cond::all(vec![])),
+		)
+		.on_conflict(&["title"])
+		.do_nothing();
+	let count = sb.exec(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(count, 0);
+
+	Ok(())
+}
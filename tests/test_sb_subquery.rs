@@ -0,0 +1,138 @@
+mod utils;
+
+use serial_test::serial;
+use sqlb::{Cond, SqlBuilder};
+use sqlx::{Pool, Postgres};
+use std::error::Error;
+use utils::init_db;
+
+/// Insert the projects A and B with the todos `{prefix}-00`, `{prefix}-01` (project A), and `{prefix}-02` (project B).
+async fn insert_project_todos(db_pool: &Pool<Postgres>, prefix: &str) -> Result<(), Box<dyn Error>> {
+	let mut project_ids = Vec::new();
+	for name in ["project A", "project B"] {
+		let (id,): (i64,) = sqlb::insert()
+			.table("project")
+			.data(vec![("name", name).into()])
+			.returning(&["id"])
+			.fetch_one(db_pool)
+			.await?;
+		project_ids.push(id);
+	}
+	for (idx, project_id) in [project_ids[0], project_ids[0], project_ids[1]].iter().enumerate() {
+		let title = format!("{prefix}-{:0>2}", idx);
+		let fields = vec![("title", title).into(), ("project_id", *project_id).into()];
+		sqlb::insert().table("todo").data(fields).exec(db_pool).await?;
+	}
+	Ok(())
+}
+
+#[test]
+fn sb_subquery_ok_renumbered_sql() {
+	let sb = sqlb::select()
+		.table_select(sqlb::select().table("todo").and_where("id", ">", 1), "t")
+		.columns(&["t.title"])
+		.and_where_eq("t.title", "a")
+		.and_where_select(
+			"t.project_id",
+			"IN",
+			sqlb::select().table("project").columns(&["id"]).and_where_eq("name", "b"),
+		)
+		.and_where_cond(Cond::not_exists(
+			sqlb::select().table("project").and_where_eq("name", "c").limit(1),
+		))
+		.and_where_eq("t.description", "d");
+
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT "t"."title" FROM (SELECT * FROM "todo" WHERE "id" > $1) AS "t" WHERE "t"."title" = $2 AND "t"."project_id" IN (SELECT "id" FROM "project" WHERE "name" = $3) AND NOT EXISTS (SELECT * FROM "project" WHERE "name" = $4 LIMIT 1) AND "t"."description" = $5 "#
+	);
+	assert_eq!(sb.vals().count(), 5);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_subquery_ok_where_select() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_prefix = "sb_subquery_ok_where_select";
+	insert_project_todos(&db_pool, fx_prefix).await?;
+
+	// -- Exec
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where("title", "LIKE", format!("{fx_prefix}%"))
+		.and_where_select(
+			"project_id",
+			"IN",
+			sqlb::select()
+				.table("project")
+				.columns(&["id"])
+				.and_where_eq("name", "project B"),
+		);
+	let titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(titles, vec![(format!("{fx_prefix}-02"),)]);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_subquery_ok_exists() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_prefix = "sb_subquery_ok_exists";
+	insert_project_todos(&db_pool, fx_prefix).await?;
+
+	// -- Exec
+	let project_sub = |name: &str| sqlb::select().table("project").and_where_eq("name", name.to_string());
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where("title", "LIKE", format!("{fx_prefix}%"))
+		.and_where_exists(project_sub("project B"))
+		.order_by("title");
+	let titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.and_where("title", "LIKE", format!("{fx_prefix}%"))
+		.and_where_not_exists(project_sub("project B"));
+	let not_titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(titles.len(), 3);
+	assert_eq!(titles[0], (format!("{fx_prefix}-00"),));
+	assert!(not_titles.is_empty());
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_subquery_ok_derived_table() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_prefix = "sb_subquery_ok_derived_table";
+	insert_project_todos(&db_pool, fx_prefix).await?;
+
+	// -- Exec
+	let sub = sqlb::select().table("todo").columns(&["title", "project_id"]).and_where(
+		"title",
+		"LIKE",
+		format!("{fx_prefix}%"),
+	);
+	let sb = sqlb::select()
+		.table_select(sub, "t")
+		.columns(&["t.title"])
+		.and_where("t.title", ">", format!("{fx_prefix}-00"))
+		.order_by("!t.title");
+	let titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(titles, vec![(format!("{fx_prefix}-02"),), (format!("{fx_prefix}-01"),)]);
+
+	Ok(())
+}
@@ -1,6 +1,6 @@
 mod utils;
 
-use sqlb::{Field, Raw, SqlBuilder};
+use sqlb::{Cond, Expr, Field, Raw, SqlBuilder};
 use sqlx::types::time::OffsetDateTime;
 use std::error::Error;
 use utils::{init_db, util_fetch_all_todos, util_insert_todo};
@@ -16,13 +16,71 @@ fn sb_update_err_all_sql_panic() {
 	// should panic
 }
 
+#[test]
+fn sb_update_err_all_try_sql() {
+	let fields = vec![("title", "test - title").into()];
+	let sb = sqlb::update().table("todo").data(fields);
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::UnguardedUpdate { .. })),
+		"should be UnguardedUpdate error, but was: {:?}",
+		res
+	);
+}
+
+#[test]
+fn sb_update_err_empty_data() {
+	let sb = sqlb::update().table("todo").and_where_eq("id", 123);
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::EmptySet { .. })),
+		"should be EmptySet error, but was: {:?}",
+		res
+	);
+}
+
 #[test]
 fn sb_update_ok_all_sql() {
-	let sb = sqlb::update_all().table("todo");
+	let fields = vec![("title", "test - title").into()];
+	let sb = sqlb::update_all().table("todo").data(fields);
 	sb.sql();
 	// should pass
 }
 
+#[serial]
+#[tokio::test]
+async fn sb_update_err_exec_all() -> Result<(), Box<dyn Error>> {
+	let db_pool = init_db().await?;
+
+	// -- Fixtures
+	let todo_id_1 = util_insert_todo(&db_pool, "test - title 01").await?;
+
+	// -- Exec
+	let fields = vec![("title", "test - new title").into()];
+	let sb = sqlb::update().table("todo").data(fields);
+	let res = sb.exec(&db_pool).await;
+
+	// -- Check
+	assert!(
+		matches!(res, Err(sqlb::Error::UnguardedUpdate { .. })),
+		"should be UnguardedUpdate error, but was: {:?}",
+		res
+	);
+	let todos = util_fetch_all_todos(&db_pool).await?;
+	assert_eq!("test - title 01", todos[0].title);
+
+	// -- Clean
+	sqlb::delete()
+		.table("todo")
+		.and_where_eq("id", todo_id_1)
+		.exec(&db_pool)
+		.await?;
+
+	Ok(())
+}
+
 #[serial]
 #[tokio::test]
 async fn sb_update_ok_exec_all() -> Result<(), Box<dyn Error>> {
@@ -165,3 +223,254 @@ async fn sb_update_ok_raw() -> Result<(), Box<dyn Error>> {
 
 	Ok(())
 }
+
+#[serial]
+#[tokio::test]
+async fn sb_update_ok_from() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let mut project_ids = Vec::new();
+	for name in ["staged A", "other B"] {
+		let sb = sqlb::insert()
+			.table("project")
+			.data(vec![("name", name).into()])
+			.returning(&["id"]);
+		let (id,): (i64,) = sb.fetch_one(&db_pool).await?;
+		project_ids.push(id);
+	}
+	for (title, project_id) in [("title 01", project_ids[0]), ("title 02", project_ids[1])] {
+		let fields = vec![("title", title).into(), ("project_id", project_id).into()];
+		sqlb::insert().table("todo").data(fields).exec(&db_pool).await?;
+	}
+
+	// -- Exec
+	let sb = sqlb::update()
+		.table("todo")
+		.data(vec![("title", "updated").into()])
+		.data_cols(&[("description", "p.name")])
+		.from_as("project", "p")
+		.and_where_cond(Cond::cols_eq("todo.project_id", "p.id"))
+		.and_where("p.name", "LIKE", "staged%")
+		.returning(&["title", "description"]);
+	let rows: Vec<(String, String)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"UPDATE "todo" SET "title" = $1, "description" = "p"."name" FROM "project" AS "p" WHERE "todo"."project_id" = "p"."id" AND "p"."name" LIKE $2 RETURNING "title", "description" "#
+	);
+	assert_eq!(rows, vec![("updated".to_string(), "staged A".to_string())]);
+	let todos = util_fetch_all_todos(&db_pool).await?;
+	let mut titles: Vec<&str> = todos.iter().map(|t| t.title.as_str()).collect();
+	titles.sort();
+	assert_eq!(titles, vec!["title 02", "updated"]);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_update_ok_expr() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fields = vec![("title", "title 01").into(), ("project_id", 10).into()];
+	sqlb::insert().table("todo").data(fields).exec(&db_pool).await?;
+
+	// -- Exec
+	let fields: Vec<Field> = vec![
+		("title", Expr::new(r#"{} || "title" || {}"#).bind("new ").bind("!")).into(),
+		("project_id", Expr::new(r#""project_id" + {}"#).bind(5)).into(),
+	];
+	let sb = sqlb::update()
+		.table("todo")
+		.data(fields)
+		.and_where("title", "=", Expr::new("lower({})").bind("TITLE 01"))
+		.returning(&["title", "project_id"]);
+	let row: (String, i64) = sb.fetch_one(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"UPDATE "todo" SET "title" = $1 || "title" || $2, "project_id" = "project_id" + $3 WHERE "title" = lower($4) RETURNING "title", "project_id" "#
+	);
+	assert_eq!(row, ("new title 01!".to_string(), 15));
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_update_ok_expr_nested() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fields = vec![("title", "title 01").into(), ("project_id", 10).into()];
+	sqlb::insert().table("todo").data(fields).exec(&db_pool).await?;
+
+	// -- Exec
+	let fields: Vec<Field> = vec![
+		(
+			"title",
+			Expr::new("upper({}) || '{{}}'").bind(Expr::new("lower({})").bind("New Title")),
+		)
+			.into(),
+		("project_id", Expr::new("{} + {}").bind(Raw(r#""project_id""#)).bind(5)).into(),
+	];
+	let sb = sqlb::update()
+		.table("todo")
+		.data(fields)
+		.and_where_eq("title", "title 01")
+		.returning(&["title", "project_id"]);
+	let row: (String, i64) = sb.fetch_one(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"UPDATE "todo" SET "title" = upper(lower($1)) || '{}', "project_id" = "project_id" + $2 WHERE "title" = $3 RETURNING "title", "project_id" "#
+	);
+	assert_eq!(row, ("NEW TITLE{}".to_string(), 15));
+
+	Ok(())
+}
+
+#[test]
+fn sb_update_err_expr_bind_mismatch() {
+	let fields: Vec<Field> = vec![("project_id", Expr::new(r#""project_id" + {}"#)).into()];
+	let res = sqlb::update().table("todo").data(fields).and_where_eq("id", 1).try_sql();
+
+	assert!(
+		matches!(res, Err(sqlb::Error::ExprBindMismatch { .. })),
+		"should be ExprBindMismatch error, but was: {:?}",
+		res
+	);
+
+	// the nested expr is checked too (and the `{{}}` literal is not a placeholder)
+	let fields: Vec<Field> = vec![("title", Expr::new("coalesce({}, '{{}}')").bind(Expr::new("lower({})"))).into()];
+	let res = sqlb::update().table("todo").data(fields).and_where_eq("id", 1).try_sql();
+
+	assert!(
+		matches!(
+			res,
+			Err(sqlb::Error::ExprBindMismatch {
+				expected: 1,
+				actual: 0,
+				..
+			})
+		),
+		"should be ExprBindMismatch error, but was: {:?}",
+		res
+	);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_update_ok_with_version() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	sqlx::query("ALTER TABLE todo ADD COLUMN version bigint NOT NULL DEFAULT 0")
+		.execute(&db_pool)
+		.await?;
+	let todo_id = util_insert_todo(&db_pool, "title 01").await?;
+	let update_sb = |title: &'static str, version: i64| {
+		sqlb::update()
+			.table("todo")
+			.data(vec![("title", title).into()])
+			.and_where_eq("id", todo_id)
+			.with_version("version", version)
+	};
+
+	// -- Exec
+	let count = update_sb("title 02", 0).exec(&db_pool).await?;
+	let stale_res = update_sb("title 03", 0).exec(&db_pool).await;
+	let sb = update_sb("title 04", 1).returning(&["title", "version"]);
+	let row: (String, i64) = sb.fetch_one(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"UPDATE "todo" SET "title" = $1, "version" = "version" + 1 WHERE "id" = $2 AND "version" = $3 RETURNING "title", "version" "#
+	);
+	assert_eq!(count, 1);
+	assert!(
+		matches!(&stale_res, Err(sqlb::Error::StaleVersion { table }) if table == "todo"),
+		"should be StaleVersion error, but was: {:?}",
+		stale_res
+	);
+	assert_eq!(row, ("title 04".to_string(), 2));
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_update_ok_with_version_fetch() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	sqlx::query("ALTER TABLE todo ADD COLUMN version bigint NOT NULL DEFAULT 0")
+		.execute(&db_pool)
+		.await?;
+	let todo_id = util_insert_todo(&db_pool, "title 01").await?;
+	// Note: Version only update (no data).
+	let version_sb = |version: i64| {
+		sqlb::update()
+			.table("todo")
+			.and_where_eq("id", todo_id)
+			.with_version("version", version)
+			.returning(&["version"])
+	};
+
+	// -- Exec
+	let row: Option<(i64,)> = version_sb(0).fetch_optional(&db_pool).await?;
+	let stale_optional_res = version_sb(0).fetch_optional::<_, (i64,)>(&db_pool).await;
+	let rows: Vec<(i64,)> = version_sb(1).fetch_all(&db_pool).await?;
+	let stale_all_res = version_sb(1).fetch_all::<_, (i64,)>(&db_pool).await;
+
+	// -- Check
+	assert_eq!(
+		version_sb(1).sql(),
+		r#"UPDATE "todo" SET "version" = "version" + 1 WHERE "id" = $1 AND "version" = $2 RETURNING "version" "#
+	);
+	assert_eq!(row, Some((1,)));
+	assert!(
+		matches!(stale_optional_res, Err(sqlb::Error::StaleVersion { .. })),
+		"should be StaleVersion error, but was: {:?}",
+		stale_optional_res
+	);
+	assert_eq!(rows, vec![(2,)]);
+	assert!(
+		matches!(stale_all_res, Err(sqlb::Error::StaleVersion { .. })),
+		"should be StaleVersion error, but was: {:?}",
+		stale_all_res
+	);
+
+	Ok(())
+}
+
+#[test]
+fn sb_update_ok_with_version_expr_sql() {
+	let sb = sqlb::update()
+		.table("todo")
+		.data(vec![("title", "title 02").into()])
+		.and_where_eq("id", 1)
+		.with_version("version", Expr::new("{} - 1").bind(3));
+
+	assert_eq!(
+		sb.sql(),
+		r#"UPDATE "todo" SET "title" = $1, "version" = "version" + 1 WHERE "id" = $2 AND "version" = $3 - 1 "#
+	);
+}
+
+#[test]
+fn sb_update_err_with_version_in_data() {
+	let res = sqlb::update()
+		.table("todo")
+		.data(vec![("title", "title 02").into(), ("version", 4).into()])
+		.and_where_eq("id", 1)
+		.with_version("version", 3)
+		.try_sql();
+
+	assert!(
+		matches!(&res, Err(sqlb::Error::VersionInData { name }) if name == "version"),
+		"should be VersionInData error, but was: {:?}",
+		res
+	);
+}
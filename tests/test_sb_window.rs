@@ -0,0 +1,133 @@
+mod utils;
+
+use serial_test::serial;
+use sqlb::{SqlBuilder, WindowFn};
+use std::error::Error;
+use utils::init_db;
+
+#[test]
+fn sb_window_ok_sql_binds() {
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["id"])
+		.window_fn(
+			WindowFn::expr(sqlb::expr(r#"lag("title", {}, {})"#).bind(1).bind("none"))
+				.over(sqlb::window().order_bys(&["id"]))
+				.alias("prev_title"),
+		)
+		.window_fn(WindowFn::count_all().over_named("w").alias("running_count"))
+		.and_where("title", "LIKE", "todo%")
+		.window(
+			"w",
+			sqlb::window()
+				.partition_by(&["project_id"])
+				.add_order_by(sqlb::order_expr(sqlb::expr(r#"abs("id" - {})"#).bind(10)))
+				.frame("ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW"),
+		)
+		.add_order_by(sqlb::order_window(WindowFn::rank().over_named("w")).desc());
+
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT "id", lag("title", $1, $2) OVER (ORDER BY "id") AS "prev_title", count(*) OVER "w" AS "running_count" FROM "todo" WHERE "title" LIKE $3 WINDOW "w" AS (PARTITION BY "project_id" ORDER BY abs("id" - $4) ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) ORDER BY rank() OVER "w" DESC "#
+	);
+	assert_eq!(sb.vals().count(), 4);
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_window_ok_partition() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	let fx_data: &[(&str, i64)] = &[("title 01", 1), ("title 02", 2), ("title 03", 1), ("title 04", 1)];
+	for (title, project_id) in fx_data {
+		let fields = vec![("title", *title).into(), ("project_id", *project_id).into()];
+		sqlb::insert().table("todo").data(fields).exec(&db_pool).await?;
+	}
+
+	// -- Exec
+	let sb = sqlb::select()
+		.table("todo")
+		.columns(&["title"])
+		.window_fn(
+			WindowFn::row_number()
+				.over(sqlb::window().partition_by(&["project_id"]).order_bys(&["!title"]))
+				.alias("rn"),
+		)
+		.window_fn(
+			WindowFn::expr(sqlb::expr(r#"lag("title", {}, {})"#).bind(1).bind("none"))
+				.over_named("w")
+				.alias("prev_title"),
+		)
+		.window_fn(WindowFn::count_all().over_named("w").alias("running_count"))
+		.window("w", sqlb::window().order_bys(&["title"]))
+		.order_by("title");
+	let rows: Vec<(String, i64, String, i64)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	let rows: Vec<(&str, i64, &str, i64)> = rows.iter().map(|r| (r.0.as_str(), r.1, r.2.as_str(), r.3)).collect();
+	assert_eq!(
+		rows,
+		vec![
+			("title 01", 3, "none", 1),
+			("title 02", 1, "title 01", 2),
+			("title 03", 2, "title 02", 3),
+			("title 04", 1, "title 03", 4),
+		]
+	);
+
+	Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn sb_window_ok_order_by() -> Result<(), Box<dyn Error>> {
+	// -- Setup & Fixtures
+	let db_pool = init_db().await?;
+	for title in ["title 01", "title 02", "title 03"] {
+		sqlb::insert()
+			.table("todo")
+			.data(vec![("title", title).into()])
+			.exec(&db_pool)
+			.await?;
+	}
+
+	// -- Exec
+	let sb = sqlb::select().table("todo").columns(&["title"]).add_order_by(
+		sqlb::order_window(
+			WindowFn::row_number()
+				.over(sqlb::window().add_order_by(sqlb::order_expr(sqlb::expr(r#"("title" = {})"#).bind("title 02")))),
+		)
+		.desc(),
+	);
+	let titles: Vec<(String,)> = sb.fetch_all(&db_pool).await?;
+
+	// -- Check
+	assert_eq!(
+		sb.sql(),
+		r#"SELECT "title" FROM "todo" ORDER BY row_number() OVER (ORDER BY ("title" = $1)) DESC "#
+	);
+	assert_eq!(titles[0].0, "title 02");
+
+	Ok(())
+}
+
+#[test]
+fn sb_window_err_bind_mismatch() {
+	let sb = sqlb::select()
+		.table("todo")
+		.window_fn(WindowFn::expr(sqlb::expr(r#"ntile({})"#)).over(sqlb::window().order_bys(&["id"])));
+	let res = sb.try_sql();
+
+	assert!(
+		matches!(
+			res,
+			Err(sqlb::Error::ExprBindMismatch {
+				expected: 1,
+				actual: 0,
+				..
+			})
+		),
+		"should be ExprBindMismatch error, but was: {:?}",
+		res
+	);
+}
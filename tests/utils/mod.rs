@@ -141,19 +141,21 @@ CREATE TABLE IF NOT EXISTS todo (
 	description text,
 	ctime timestamp with time zone,
 	"desc" text,
-	status todo_status_enum
+	status todo_status_enum,
+	project_id bigint
 );"#,
 	)
 	.execute(&pool)
 	.await?;
 
 	// Create project table
-	sqlx::query("DROP TABLE IF EXISTS projects").execute(&pool).await?;
+	sqlx::query("DROP TABLE IF EXISTS project").execute(&pool).await?;
 	sqlx::query(
 		r#"
 CREATE TABLE IF NOT EXISTS project (
   id bigserial,
-  name text
+  name text,
+	CONSTRAINT project_name_key UNIQUE (name)
 );"#,
 	)
 	.execute(&pool)